@@ -3,22 +3,104 @@ use proc_macro::TokenStream;
 use quote::quote;
 
 #[derive(darling::FromDeriveInput)]
-#[darling(attributes(aggregate_root), supports(struct_named))]
+#[darling(attributes(aggregate_root), supports(struct_any, enum_any))]
 struct AggregateRoot {
     ident: syn::Ident,
+    vis: syn::Visibility,
     generics: syn::Generics,
     data: darling::ast::Data<darling::util::Ignored, AggregateRootField>,
+    event_sourced: Option<EventSourcedMarker>,
+    #[darling(multiple, rename = "invariant")]
+    invariants: Vec<syn::Path>,
+    builder: Option<BuilderMarker>,
+    created: Option<syn::Path>,
+    track_changes: Option<TrackChangesMarker>,
 }
 
+#[derive(darling::FromMeta)]
+struct EventSourcedMarker;
+
+#[derive(darling::FromMeta)]
+struct BuilderMarker;
+
+#[derive(darling::FromMeta)]
+struct TrackChangesMarker;
+
+#[derive(darling::FromMeta)]
+struct DefaultMarker;
+
 #[derive(darling::FromMeta)]
 struct DomainEventsMarker;
 
+#[derive(darling::FromMeta)]
+struct VersionMarker;
+
+#[derive(darling::FromMeta)]
+struct ChangesMarker;
+
+#[derive(darling::FromMeta)]
+struct StateMarker;
+
+#[derive(darling::FromMeta)]
+struct SoftDeleteMarker;
+
+#[derive(darling::FromMeta)]
+struct TenantMarker;
+
 #[derive(darling::FromField)]
-#[darling(attributes(aggregate_root))]
+#[darling(attributes(aggregate_root), forward_attrs(entity))]
 struct AggregateRootField {
     ident: Option<syn::Ident>,
     ty: syn::Type,
+    attrs: Vec<syn::Attribute>,
     domain_events: Option<DomainEventsMarker>,
+    version: Option<VersionMarker>,
+    changes: Option<ChangesMarker>,
+    state: Option<StateMarker>,
+    transition_event: Option<syn::Path>,
+    soft_delete: Option<SoftDeleteMarker>,
+    tenant: Option<TenantMarker>,
+    default: Option<DefaultMarker>,
+}
+
+impl AggregateRootField {
+    /// Whether the field is an identity field, i.e. tagged with `#[entity(id)]`.
+    fn is_id(&self) -> bool {
+        self.entity_attrs().0
+    }
+
+    /// Whether the field is an identity field with a generator, i.e. tagged with
+    /// `#[entity(id, generator = ...)]`.
+    fn is_generated_id(&self) -> bool {
+        let (id, generator) = self.entity_attrs();
+
+        id && generator
+    }
+
+    /// Whether the field is tagged with the `id` and `generator` entity attributes, respectively.
+    fn entity_attrs(&self) -> (bool, bool) {
+        let mut id = false;
+        let mut generator = false;
+
+        for attr in &self.attrs {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("id") {
+                    id = true;
+                } else if meta.path.is_ident("generator") {
+                    generator = true;
+                }
+
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+
+                Ok(())
+            })
+            .expect("Invalid `entity` attribute");
+        }
+
+        (id, generator)
+    }
 }
 
 pub fn derive(input: TokenStream) -> TokenStream {
@@ -26,37 +108,266 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
     let AggregateRoot {
         ident,
+        vis,
         generics,
         data,
-        ..
+        event_sourced,
+        invariants,
+        builder,
+        created,
+        track_changes,
     } = match AggregateRoot::from_derive_input(&derive_input) {
         Ok(receiver) => receiver,
         Err(e) => return TokenStream::from(e.write_errors()),
     };
 
-    let fields = data.take_struct().unwrap();
+    // Enum aggregate roots have no fields to be tagged.
+    let fields = match data {
+        darling::ast::Data::Struct(fields) => fields,
+        darling::ast::Data::Enum(_) => darling::ast::Fields::new(darling::ast::Style::Unit, vec![]),
+    };
+
+    let builder = match (builder, created) {
+        (Some(_), created) => {
+            if fields.style != darling::ast::Style::Struct {
+                panic!("Builders are only supported for structs with named fields");
+            }
+
+            Some(derive_builder(
+                &ident,
+                &vis,
+                &generics,
+                &fields.fields,
+                created,
+            ))
+        }
+        (None, Some(_)) => panic!("The `created` attribute requires the `builder` attribute"),
+        (None, None) => None,
+    };
 
-    derive_aggregate_root(ident, generics, fields)
+    // Aggregate roots should only reference one another by identity. Generic field types cannot be
+    // named outside of the aggregate root's generic context, hence they are not checked.
+    let field_assertions = generics.params.is_empty().then(|| {
+        let field_ty = fields.fields.iter().map(|f| &f.ty);
+
+        quote! {
+            const _: () = {
+                #(
+                    let _ = <#field_ty as ddd_rs::__private::AggregateRootsMustBeReferencedByIdentity<_>>::assert;
+                )*
+            };
+        }
+    });
+
+    let aggregate_root: proc_macro2::TokenStream = derive_aggregate_root(
+        ident,
+        vis,
+        generics,
+        fields,
+        event_sourced.is_some(),
+        invariants,
+        track_changes.is_some(),
+    )
+    .into();
+
+    quote! {
+        #aggregate_root
+
+        #builder
+
+        #field_assertions
+    }
+    .into()
 }
 
 fn derive_aggregate_root(
     ident: syn::Ident,
+    vis: syn::Visibility,
     generics: syn::Generics,
     fields: darling::ast::Fields<AggregateRootField>,
+    event_sourced: bool,
+    invariants: Vec<syn::Path>,
+    track_changes: bool,
 ) -> TokenStream {
+    let (change_tracked, as_change_tracked) = if track_changes {
+        if fields.style != darling::ast::Style::Struct {
+            panic!("Change tracking is only supported for structs with named fields");
+        }
+
+        derive_change_tracked(&ident, &vis, &generics, &fields.fields)
+    } else {
+        if fields.iter().any(|f| f.changes.is_some()) {
+            panic!("The `changes` field requires the `track_changes` attribute");
+        }
+
+        Default::default()
+    };
+
+    let state_transition =
+        derive_state_transition(&ident, &vis, &generics, &fields.fields, track_changes);
+
+    let fields = fields.fields;
+
+    let member = crate::field_members(fields.iter().map(|f| f.ident.as_ref()));
+
+    let (versioned, as_versioned) = fields
+        .iter()
+        .zip(&member)
+        .find(|(f, _)| f.version.is_some())
+        .map(|(_, version_ident)| {
+            let versioned = quote! {
+                impl #generics ddd_rs::domain::Versioned for #ident #generics {
+                    fn version(&self) -> u64 {
+                        self.#version_ident
+                    }
+
+                    fn set_version(&mut self, version: u64) {
+                        self.#version_ident = version;
+                    }
+                }
+            };
+
+            let as_versioned = quote! {
+                fn as_versioned(&self) -> Option<&dyn ddd_rs::domain::Versioned> {
+                    Some(self)
+                }
+
+                fn as_versioned_mut(&mut self) -> Option<&mut dyn ddd_rs::domain::Versioned> {
+                    Some(self)
+                }
+            };
+
+            (versioned, as_versioned)
+        })
+        .unwrap_or_else(|| {
+            if event_sourced {
+                panic!("Event-sourced aggregate roots must have a `version` field");
+            }
+
+            Default::default()
+        });
+
+    let (soft_deletable, as_soft_deletable) = fields
+        .iter()
+        .zip(&member)
+        .find(|(f, _)| f.soft_delete.is_some())
+        .map(|(_, deleted_at_ident)| {
+            let soft_deletable = quote! {
+                impl #generics ddd_rs::domain::SoftDeletable for #ident #generics {
+                    fn deleted_at(&self) -> Option<std::time::SystemTime> {
+                        self.#deleted_at_ident
+                    }
+
+                    fn set_deleted_at(&mut self, deleted_at: Option<std::time::SystemTime>) {
+                        self.#deleted_at_ident = deleted_at;
+                    }
+                }
+            };
+
+            let as_soft_deletable = quote! {
+                fn as_soft_deletable(&self) -> Option<&dyn ddd_rs::domain::SoftDeletable> {
+                    Some(self)
+                }
+
+                fn as_soft_deletable_mut(&mut self) -> Option<&mut dyn ddd_rs::domain::SoftDeletable> {
+                    Some(self)
+                }
+            };
+
+            (soft_deletable, as_soft_deletable)
+        })
+        .unwrap_or_default();
+
+    let tenant_scoped = fields
+        .iter()
+        .zip(&member)
+        .find(|(f, _)| f.tenant.is_some())
+        .map(|(f, tenant_id_ident)| {
+            let tenant_id_ty = &f.ty;
+
+            quote! {
+                impl #generics ddd_rs::domain::TenantScoped for #ident #generics {
+                    type TenantId = #tenant_id_ty;
+
+                    fn tenant_id(&self) -> &Self::TenantId {
+                        &self.#tenant_id_ident
+                    }
+                }
+            }
+        });
+
+    // Only `EntityCollection` fields actually clear their changes, which is resolved by the compiler
+    // since their types cannot be told apart here.
+    let clear_collection_changes = (!fields.is_empty()).then(|| {
+        quote! {
+            fn clear_collection_changes(&mut self) {
+                use ddd_rs::__private::{ClearEntityCollectionChanges, ClearFieldChanges};
+
+                #((&mut ddd_rs::__private::Field(&mut self.#member)).clear_changes();)*
+            }
+        }
+    });
+
+    let pending_changes = fields
+        .iter()
+        .zip(&member)
+        .find(|(f, _)| f.domain_events.is_some())
+        .map(|(_, domain_events_ident)| {
+            // Event-sourced aggregates only change through their domain events.
+            let pending_changes = if event_sourced {
+                quote!(self.#domain_events_ident.len() as u64)
+            } else {
+                quote!(self.#domain_events_ident.len().max(1) as u64)
+            };
+
+            quote! {
+                fn pending_changes(&self) -> u64 {
+                    #pending_changes
+                }
+
+                fn clear_domain_events(&mut self) {
+                    self.#domain_events_ident.clear();
+                }
+            }
+        });
+
+    let (invariants, as_invariants) = if invariants.is_empty() {
+        // Hand-written implementations of `Invariants`, if any, are picked up instead.
+        let as_invariants = quote! {
+            fn as_invariants(&self) -> Option<&dyn ddd_rs::domain::Invariants> {
+                #[allow(unused_imports)]
+                use ddd_rs::__private::{AsInvariants, AsNoInvariants};
+
+                (&ddd_rs::__private::Wrap(self)).as_invariants()
+            }
+        };
+
+        (Default::default(), as_invariants)
+    } else {
+        derive_invariants(&ident, &generics, invariants)
+    };
+
     let aggregate_root_ex = fields
         .into_iter()
-        .find_map(|f| {
+        .zip(&member)
+        .find_map(|(f, domain_events_ident)| {
             f.domain_events.map(|_| {
-                let domain_events_ident = f.ident.unwrap();
                 let domain_events_ty = map_domain_event_ty(f.ty);
 
+                let apply_domain_event = event_sourced.then(|| {
+                    quote! {
+                        ddd_rs::domain::EventSourced::apply(self, &domain_event);
+                    }
+                });
+
                 quote! {
                     impl #generics #ident #generics {
                         fn register_domain_event(
                             &mut self,
                             domain_event: <Self as ddd_rs::domain::AggregateRootEx>::DomainEvent
                         ) {
+                            #apply_domain_event
+
                             self.#domain_events_ident.push(domain_event);
                         }
                     }
@@ -71,16 +382,364 @@ fn derive_aggregate_root(
                 }
             })
         })
-        .unwrap_or_default();
+        .unwrap_or_else(|| {
+            if event_sourced {
+                panic!("Event-sourced aggregate roots must have a `domain_events` field");
+            }
+
+            Default::default()
+        });
 
     quote! {
-        impl #generics ddd_rs::domain::AggregateRoot for #ident #generics {}
+        impl #generics ddd_rs::domain::AggregateRoot for #ident #generics {
+            #as_versioned
+            #as_invariants
+            #as_change_tracked
+            #clear_collection_changes
+            #pending_changes
+            #as_soft_deletable
+        }
+
+        #versioned
+
+        #invariants
+
+        #change_tracked
+
+        #soft_deletable
+
+        #tenant_scoped
+
+        #state_transition
 
         #aggregate_root_ex
     }
     .into()
 }
 
+fn derive_invariants(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    invariants: Vec<syn::Path>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let check_invariant = invariants.into_iter().map(|invariant| {
+        let name = invariant.segments.last().unwrap().ident.to_string();
+        let message = format!("Invariant `{}` does not hold", name);
+
+        quote! {
+            if !#invariant(self) {
+                violations.push(ddd_rs::domain::InvariantViolation::new(#name, #message));
+            }
+        }
+    });
+
+    let invariants = quote! {
+        impl #generics ddd_rs::domain::Invariants for #ident #generics {
+            fn check_invariants(&self) -> Result<(), ddd_rs::domain::InvariantError> {
+                let mut violations = Vec::<ddd_rs::domain::InvariantViolation>::new();
+
+                #(#check_invariant)*
+
+                if violations.is_empty() {
+                    Ok(())
+                } else {
+                    Err(ddd_rs::domain::InvariantError::new(violations))
+                }
+            }
+        }
+    };
+
+    let as_invariants = quote! {
+        fn as_invariants(&self) -> Option<&dyn ddd_rs::domain::Invariants> {
+            Some(self)
+        }
+    };
+
+    (invariants, as_invariants)
+}
+
+fn derive_change_tracked(
+    ident: &syn::Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    fields: &[AggregateRootField],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let changes_ident = fields
+        .iter()
+        .find(|f| f.changes.is_some())
+        .and_then(|f| f.ident.as_ref())
+        .expect("Change-tracked aggregate roots must have a `changes` field");
+
+    // Identities, domain events, versions, states, deletion marks, tenants and changes are not
+    // modified through setters.
+    let (field_ident, field_ty) = fields
+        .iter()
+        .filter(|f| {
+            !f.is_id()
+                && f.domain_events.is_none()
+                && f.version.is_none()
+                && f.state.is_none()
+                && f.soft_delete.is_none()
+                && f.tenant.is_none()
+                && f.changes.is_none()
+        })
+        .map(|f| (f.ident.as_ref().unwrap(), &f.ty))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    let setter_ident = field_ident
+        .iter()
+        .map(|f| quote::format_ident!("set_{}", f));
+
+    let setter_doc = field_ident
+        .iter()
+        .map(|f| format!("Sets the `{}` field, recording its modification.", f));
+
+    let name = field_ident.iter().map(|f| f.to_string());
+
+    let change_tracked = quote! {
+        impl #generics #ident #generics {
+            #(
+                #[doc = #setter_doc]
+                #vis fn #setter_ident(&mut self, #field_ident: impl Into<#field_ty>) {
+                    self.#field_ident = #field_ident.into();
+                    self.#changes_ident.record(#name);
+                }
+            )*
+        }
+
+        impl #generics ddd_rs::domain::ChangeTracked for #ident #generics {
+            fn changes(&self) -> &ddd_rs::domain::ChangeSet {
+                &self.#changes_ident
+            }
+
+            fn clear_changes(&mut self) {
+                self.#changes_ident.clear();
+            }
+        }
+    };
+
+    let as_change_tracked = quote! {
+        fn as_change_tracked(&self) -> Option<&dyn ddd_rs::domain::ChangeTracked> {
+            Some(self)
+        }
+
+        fn as_change_tracked_mut(&mut self) -> Option<&mut dyn ddd_rs::domain::ChangeTracked> {
+            Some(self)
+        }
+    };
+
+    (change_tracked, as_change_tracked)
+}
+
+fn derive_state_transition(
+    ident: &syn::Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    fields: &[AggregateRootField],
+    track_changes: bool,
+) -> Option<proc_macro2::TokenStream> {
+    let mut states = fields.iter().filter(|f| f.state.is_some());
+
+    let state = match states.next() {
+        Some(state) => state,
+        None => {
+            if fields.iter().any(|f| f.transition_event.is_some()) {
+                panic!("The `transition_event` attribute requires the `state` attribute");
+            }
+
+            return None;
+        }
+    };
+
+    if states.next().is_some() {
+        panic!("Aggregate roots may only have a single `state` field");
+    }
+
+    let state_ident = state
+        .ident
+        .as_ref()
+        .expect("The `state` field must be a named field");
+    let state_ty = &state.ty;
+    let name = state_ident.to_string();
+
+    let record_change = track_changes.then(|| {
+        let changes_ident = fields
+            .iter()
+            .find(|f| f.changes.is_some())
+            .and_then(|f| f.ident.as_ref());
+
+        quote! {
+            self.#changes_ident.record(#name);
+        }
+    });
+
+    let register_transition_event = state.transition_event.as_ref().map(|transition_event| {
+        if !fields.iter().any(|f| f.domain_events.is_some()) {
+            panic!("The `transition_event` attribute requires a `domain_events` field");
+        }
+
+        quote! {
+            let domain_event = #transition_event(self, &previous, &self.#state_ident);
+
+            self.register_domain_event(domain_event);
+        }
+    });
+
+    let previous = register_transition_event
+        .as_ref()
+        .map(|_| quote!(let previous =));
+
+    Some(quote! {
+        impl #generics #ident #generics {
+            /// Transitions the state of the aggregate root, or fails if the transition is not
+            /// allowed.
+            #vis fn transition_to(
+                &mut self,
+                next: #state_ty,
+            ) -> Result<(), ddd_rs::domain::IllegalTransition<#state_ty>> {
+                #previous <#state_ty as ddd_rs::domain::StateMachine>::transition_to(
+                    &mut self.#state_ident,
+                    next,
+                )?;
+
+                #record_change
+
+                #register_transition_event
+
+                Ok(())
+            }
+        }
+    })
+}
+
+fn derive_builder(
+    ident: &syn::Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    fields: &[AggregateRootField],
+    created: Option<syn::Path>,
+) -> proc_macro2::TokenStream {
+    let builder_ident = quote::format_ident!("{}Builder", ident);
+    let builder_doc = format!("Builder for [`{}`].", ident);
+
+    // Generated identities, domain events, versions, deletion marks and changes are not set
+    // through the builder.
+    let (settable, init) = fields
+        .iter()
+        .map(|f| {
+            let field_ident = f.ident.as_ref().unwrap();
+            let name = field_ident.to_string();
+
+            if f.is_generated_id() {
+                (false, quote!(#ident::generate_id()))
+            } else if f.domain_events.is_some()
+                || f.version.is_some()
+                || f.soft_delete.is_some()
+                || f.changes.is_some()
+            {
+                (false, quote!(Default::default()))
+            } else if f.default.is_some() {
+                (true, quote!(self.#field_ident.unwrap_or_default()))
+            } else {
+                (
+                    true,
+                    quote! {
+                        self.#field_ident.ok_or(
+                            ddd_rs::domain::FactoryError::MissingField(#name)
+                        )?
+                    },
+                )
+            }
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    // Identities are generated after every other field is initialized, so that builds missing a
+    // required field do not consume them. Builds rejected by invariants still do, since invariants
+    // (and the `created` domain event) may depend on the identity.
+    let (generated, given) = fields
+        .iter()
+        .zip(init)
+        .map(|(f, init)| (f.ident.as_ref().unwrap(), init, f.is_generated_id()))
+        .partition::<Vec<_>, _>(|(_, _, is_generated_id)| *is_generated_id);
+
+    let init = given
+        .into_iter()
+        .chain(generated)
+        .map(|(field_ident, init, _)| quote!(let #field_ident = #init;));
+
+    let all_field_ident = fields.iter().map(|f| f.ident.as_ref().unwrap());
+
+    let (field_ident, field_ty) = fields
+        .iter()
+        .zip(settable)
+        .filter(|(_, settable)| *settable)
+        .map(|(f, _)| (f.ident.as_ref().unwrap(), &f.ty))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    let setter_doc = field_ident
+        .iter()
+        .map(|f| format!("Sets the `{}` field.", f));
+
+    let mutability = created.as_ref().map(|_| quote!(mut));
+
+    let register_created = created.map(|created| {
+        if !fields.iter().any(|f| f.domain_events.is_some()) {
+            panic!("The `created` attribute requires a `domain_events` field");
+        }
+
+        quote! {
+            aggregate_root.register_domain_event(#created(&aggregate_root));
+        }
+    });
+
+    quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder_ident #generics {
+            #(#field_ident: Option<#field_ty>,)*
+        }
+
+        impl #generics #ident #generics {
+            /// Creates a new builder for the aggregate root.
+            #vis fn builder() -> #builder_ident #generics {
+                #builder_ident {
+                    #(#field_ident: None,)*
+                }
+            }
+        }
+
+        impl #generics #builder_ident #generics {
+            #(
+                #[doc = #setter_doc]
+                #vis fn #field_ident(mut self, #field_ident: impl Into<#field_ty>) -> Self {
+                    self.#field_ident = Some(#field_ident.into());
+                    self
+                }
+            )*
+
+            /// Builds the aggregate root, failing if any required field is missing or any invariant
+            /// does not hold.
+            ///
+            /// Generated identities are only consumed once every required field is set, including
+            /// by builds whose invariants do not hold.
+            #vis fn build(self) -> Result<#ident #generics, ddd_rs::domain::FactoryError> {
+                #(#init)*
+
+                let #mutability aggregate_root = #ident {
+                    #(#all_field_ident,)*
+                };
+
+                #register_created
+
+                if let Some(invariants) = ddd_rs::domain::AggregateRoot::as_invariants(&aggregate_root) {
+                    invariants.check_invariants()?;
+                }
+
+                Ok(aggregate_root)
+            }
+        }
+    }
+}
+
 fn map_domain_event_ty(ty: syn::Type) -> syn::Type {
     use syn::{GenericArgument, PathArguments, Type};
 
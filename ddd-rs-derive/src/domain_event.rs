@@ -0,0 +1,83 @@
+use darling::FromDeriveInput;
+use proc_macro::TokenStream;
+use quote::quote;
+
+#[derive(darling::FromDeriveInput)]
+#[darling(attributes(domain_event), supports(struct_any, enum_any))]
+struct DomainEvent {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    data: darling::ast::Data<DomainEventVariant, darling::util::Ignored>,
+    name: Option<String>,
+    version: Option<u32>,
+}
+
+#[derive(darling::FromVariant)]
+#[darling(attributes(domain_event))]
+struct DomainEventVariant {
+    ident: syn::Ident,
+    name: Option<String>,
+    version: Option<u32>,
+}
+
+pub fn derive(input: TokenStream) -> TokenStream {
+    let derive_input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    let DomainEvent {
+        ident,
+        generics,
+        data,
+        name,
+        version,
+    } = match DomainEvent::from_derive_input(&derive_input) {
+        Ok(receiver) => receiver,
+        Err(e) => return TokenStream::from(e.write_errors()),
+    };
+
+    // Versions default to the one given to the event type itself, if any.
+    let version = version.unwrap_or(1);
+
+    let (event_type, schema_version) = match data {
+        darling::ast::Data::Struct(_) => {
+            let name = name.unwrap_or_else(|| ident.to_string());
+
+            (quote!(#name), quote!(#version))
+        }
+        darling::ast::Data::Enum(variants) if variants.is_empty() => {
+            (quote!(match *self {}), quote!(match *self {}))
+        }
+        darling::ast::Data::Enum(variants) => {
+            if name.is_some() {
+                panic!("Enum domain events must name each of their variants instead");
+            }
+
+            let variant_ident = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+            // Variant names are prefixed with the enum's, so that events of different aggregates
+            // do not collide.
+            let variant_name = variants.iter().map(|v| {
+                v.name
+                    .clone()
+                    .unwrap_or_else(|| format!("{}.{}", ident, v.ident))
+            });
+            let variant_version = variants.iter().map(|v| v.version.unwrap_or(version));
+
+            (
+                quote!(match self { #(Self::#variant_ident { .. } => #variant_name,)* }),
+                quote!(match self { #(Self::#variant_ident { .. } => #variant_version,)* }),
+            )
+        }
+    };
+
+    quote! {
+        impl #generics ddd_rs::domain::DomainEvent for #ident #generics {
+            fn event_type(&self) -> &'static str {
+                #event_type
+            }
+
+            fn schema_version(&self) -> u32 {
+                #schema_version
+            }
+        }
+    }
+    .into()
+}
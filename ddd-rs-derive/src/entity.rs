@@ -3,22 +3,47 @@ use proc_macro::TokenStream;
 use quote::quote;
 
 #[derive(darling::FromDeriveInput)]
-#[darling(attributes(entity), supports(struct_named))]
+#[darling(attributes(entity), supports(struct_any, enum_any))]
 struct Entity {
     ident: syn::Ident,
+    vis: syn::Visibility,
     generics: syn::Generics,
-    data: darling::ast::Data<darling::util::Ignored, EntityField>,
+    data: darling::ast::Data<EntityVariant, EntityField>,
+    hash: Option<HashMarker>,
+    ord: Option<OrdMarker>,
+    copy: Option<CopyMarker>,
 }
 
+#[derive(darling::FromVariant)]
+#[darling(attributes(entity))]
+struct EntityVariant {
+    ident: syn::Ident,
+    fields: darling::ast::Fields<EntityField>,
+}
+
+#[derive(darling::FromMeta)]
+struct HashMarker;
+
+#[derive(darling::FromMeta)]
+struct OrdMarker;
+
+#[derive(darling::FromMeta)]
+struct CopyMarker;
+
 #[derive(darling::FromMeta)]
 struct IdMarker;
 
+#[derive(darling::FromMeta)]
+struct IdCacheMarker;
+
 #[derive(darling::FromField)]
 #[darling(attributes(entity))]
 struct EntityField {
     ident: Option<syn::Ident>,
     ty: syn::Type,
     id: Option<IdMarker>,
+    id_cache: Option<IdCacheMarker>,
+    generator: Option<syn::Path>,
 }
 
 pub fn derive(input: TokenStream) -> TokenStream {
@@ -26,38 +51,299 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
     let Entity {
         ident,
+        vis,
         generics,
         data,
-        ..
+        hash,
+        ord,
+        copy,
     } = match Entity::from_derive_input(&derive_input) {
         Ok(entity) => entity,
         Err(e) => return TokenStream::from(e.write_errors()),
     };
 
-    let fields = data.take_struct().unwrap();
+    let copy = copy.map(|_| match &data {
+        darling::ast::Data::Struct(fields) => {
+            crate::derive_copy(&ident, &generics, fields.iter().map(|f| &f.ty))
+        }
+        darling::ast::Data::Enum(variants) => crate::derive_copy(
+            &ident,
+            &generics,
+            variants.iter().flat_map(|v| v.fields.iter().map(|f| &f.ty)),
+        ),
+    });
+
+    let (entity, composite_id_ty, generate_id) = match data {
+        darling::ast::Data::Struct(fields) => derive_struct(&ident, &generics, fields),
+        darling::ast::Data::Enum(variants) => {
+            let (entity, composite_id_ty) = derive_enum(&ident, &generics, variants);
+
+            (entity, composite_id_ty, None)
+        }
+    };
+
+    // Type aliases cannot have unused generic parameters, hence generic entities must name their
+    // composite identity type through `Entity::Id` instead.
+    let id_alias = composite_id_ty
+        .filter(|_| generics.params.is_empty())
+        .map(|id_ty| {
+            let id_alias = quote::format_ident!("{}Id", ident);
+            let doc = format!("Composite identity of [{}].", ident);
 
-    derive_entity(ident, generics, fields)
+            quote! {
+                #[doc = #doc]
+                #vis type #id_alias = #id_ty;
+            }
+        });
+
+    let hash = hash.map(|_| {
+        quote! {
+            impl #generics std::hash::Hash for #ident #generics {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    std::hash::Hash::hash(ddd_rs::domain::Entity::id(self), state);
+                }
+            }
+        }
+    });
+
+    let ord = ord.map(|_| {
+        quote! {
+            impl #generics PartialOrd for #ident #generics {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl #generics Ord for #ident #generics {
+                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    use ddd_rs::domain::Entity;
+
+                    self.id().cmp(other.id())
+                }
+            }
+        }
+    });
+
+    quote! {
+        #entity
+
+        #id_alias
+
+        #generate_id
+
+        #hash
+
+        #ord
+
+        #copy
+    }
+    .into()
 }
 
-fn derive_entity(
-    ident: syn::Ident,
-    generics: syn::Generics,
+fn derive_struct(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
     fields: darling::ast::Fields<EntityField>,
-) -> TokenStream {
-    let id_field = fields
+) -> (
+    proc_macro2::TokenStream,
+    Option<proc_macro2::TokenStream>,
+    Option<proc_macro2::TokenStream>,
+) {
+    // Unit structs are singletons, hence their identity is the unit type.
+    if fields.style == darling::ast::Style::Unit {
+        return (
+            derive_entity(ident, generics, quote!(()), quote!(&())),
+            None,
+            None,
+        );
+    }
+
+    let fields = fields.into_iter().collect::<Vec<_>>();
+    let member = crate::field_members(fields.iter().map(|f| f.ident.as_ref()));
+
+    let (id_fields, id_value) = fields
+        .iter()
+        .zip(&member)
+        .filter(|(f, _)| f.id.is_some())
+        .map(|(f, m)| (f, quote!(&self.#m)))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    if id_fields.is_empty() {
+        panic!("Missing `id` field");
+    }
+
+    let id_cache = fields
+        .iter()
+        .zip(&member)
+        .find(|(f, _)| f.id_cache.is_some())
+        .map(|(_, m)| quote!(&self.#m));
+
+    let (id_ty, id, composite) = derive_id(&id_fields, &id_value, id_cache);
+
+    let generate_id = id_fields
+        .iter()
+        .find_map(|f| f.generator.as_ref())
+        .map(|generator| {
+            if composite {
+                panic!("Composite identities do not support generators");
+            }
+
+            quote! {
+                impl #generics #ident #generics {
+                    /// Generates a new identity for the entity.
+                    pub fn generate_id() -> #id_ty {
+                        From::from(ddd_rs::domain::IdGenerator::next_id(&#generator))
+                    }
+                }
+            }
+        });
+
+    (
+        derive_entity(ident, generics, id_ty.clone(), id),
+        composite.then_some(id_ty),
+        generate_id,
+    )
+}
+
+fn derive_enum(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    variants: Vec<EntityVariant>,
+) -> (proc_macro2::TokenStream, Option<proc_macro2::TokenStream>) {
+    let (id_ty, id_arm) = variants
+        .iter()
+        .map(|v| {
+            let variant_ident = &v.ident;
+            let fields = v.fields.iter().collect::<Vec<_>>();
+            let member = crate::field_members(fields.iter().map(|f| f.ident.as_ref()));
+            let binding = crate::field_bindings("f", &member);
+
+            let is_id = if fields.iter().any(|f| f.id.is_some()) {
+                fields.iter().map(|f| f.id.is_some()).collect()
+            } else if fields.len() == 1 {
+                vec![true]
+            } else {
+                panic!(
+                    "Enum entity variants must either tag their `id` field or wrap a single entity"
+                );
+            };
+
+            let pat = crate::fields_expr(
+                quote!(Self::#variant_ident),
+                v.fields.style,
+                &member,
+                binding
+                    .iter()
+                    .zip(&is_id)
+                    .zip(&fields)
+                    .map(|((b, is_id), f)| {
+                        if *is_id || f.id_cache.is_some() {
+                            quote!(#b)
+                        } else {
+                            quote!(_)
+                        }
+                    }),
+            );
+
+            let (id_ty, id) = if fields.iter().any(|f| f.id.is_some()) {
+                let (id_fields, id_value) = fields
+                    .iter()
+                    .zip(&binding)
+                    .filter(|(f, _)| f.id.is_some())
+                    .map(|(f, b)| (*f, quote!(#b)))
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+
+                let id_cache = fields
+                    .iter()
+                    .zip(&binding)
+                    .find(|(f, _)| f.id_cache.is_some())
+                    .map(|(_, b)| quote!(#b));
+
+                let (id_ty, id, composite) = derive_id(&id_fields, &id_value, id_cache);
+
+                ((id_ty, composite), id)
+            } else {
+                // Variants that wrap a single entity delegate to its identity.
+                let ty = &fields[0].ty;
+                let b = &binding[0];
+
+                (
+                    (quote!(<#ty as ddd_rs::domain::Entity>::Id), false),
+                    quote!(ddd_rs::domain::Entity::id(#b)),
+                )
+            };
+
+            (id_ty, quote!(#pat => #id,))
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    // Every variant must share the same identity type.
+    let (id_ty, composite) = id_ty
         .into_iter()
-        .find(|f| f.id.is_some())
-        .expect("Missing `id` field");
+        .next()
+        .expect("Enum entities must have at least one variant");
+
+    (
+        derive_entity(
+            ident,
+            generics,
+            id_ty.clone(),
+            quote!(match self { #(#id_arm)* }),
+        ),
+        composite.then_some(id_ty),
+    )
+}
 
-    let id_ident = id_field.ident.unwrap();
-    let id_ty = id_field.ty;
+/// Identity type and borrowed value of the given identity fields, and whether it is composite.
+///
+/// Composite identities are tuples of every identity field, which are cached in the given
+/// `IdCache` field so that they may be borrowed.
+fn derive_id(
+    id_fields: &[&EntityField],
+    id_value: &[proc_macro2::TokenStream],
+    id_cache: Option<proc_macro2::TokenStream>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream, bool) {
+    if let ([id_field], [id_value]) = (id_fields, id_value) {
+        let id_ty = &id_field.ty;
+
+        return (quote!(#id_ty), quote!(#id_value), false);
+    }
 
+    let id_cache = id_cache.expect(
+        "Composite identities must be cached in an `#[entity(id_cache)]` field of type `IdCache`",
+    );
+
+    let id_ty = id_fields.iter().map(|f| &f.ty);
+    let index = (0..id_fields.len()).map(syn::Index::from);
+
+    let id = quote! {{
+        let id = ddd_rs::domain::IdCache::get_or_init(#id_cache, || {
+            (#(Clone::clone(#id_value),)*)
+        });
+
+        assert!(
+            #(id.#index == *#id_value)&&*,
+            "Identity components must not change once cached, unless the `IdCache` is reset"
+        );
+
+        id
+    }};
+
+    (quote!((#(#id_ty,)*)), id, true)
+}
+
+fn derive_entity(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    id_ty: proc_macro2::TokenStream,
+    id: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     quote! {
         impl #generics ddd_rs::domain::Entity for #ident #generics {
             type Id = #id_ty;
 
             fn id(&self) -> &Self::Id {
-                &self.#id_ident
+                #id
             }
         }
 
@@ -71,5 +357,4 @@ fn derive_entity(
 
         impl #generics Eq for #ident #generics {}
     }
-    .into()
 }
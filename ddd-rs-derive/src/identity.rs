@@ -0,0 +1,141 @@
+use darling::FromDeriveInput;
+use proc_macro::TokenStream;
+use quote::quote;
+
+#[derive(darling::FromDeriveInput)]
+#[darling(supports(struct_newtype))]
+struct Identity {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    data: darling::ast::Data<darling::util::Ignored, IdentityField>,
+}
+
+#[derive(darling::FromField)]
+struct IdentityField {
+    ty: syn::Type,
+}
+
+pub fn derive(input: TokenStream) -> TokenStream {
+    let derive_input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    let Identity {
+        ident,
+        generics,
+        data,
+    } = match Identity::from_derive_input(&derive_input) {
+        Ok(receiver) => receiver,
+        Err(e) => return TokenStream::from(e.write_errors()),
+    };
+
+    let field = data.take_struct().unwrap().into_iter().next().unwrap();
+
+    derive_identity(ident, generics, field)
+}
+
+fn derive_identity(
+    ident: syn::Ident,
+    generics: syn::Generics,
+    field: IdentityField,
+) -> TokenStream {
+    let value_ty = field.ty;
+
+    let serde = cfg!(feature = "serde").then(|| {
+        let mut de_generics = generics.clone();
+
+        de_generics.params.insert(0, syn::parse_quote!('de));
+
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+        quote! {
+            impl #generics ddd_rs::__private::serde::Serialize for #ident #generics {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: ddd_rs::__private::serde::Serializer,
+                {
+                    ddd_rs::__private::serde::Serialize::serialize(&self.0, serializer)
+                }
+            }
+
+            impl #de_impl_generics ddd_rs::__private::serde::Deserialize<'de>
+                for #ident #ty_generics #where_clause
+            {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ddd_rs::__private::serde::Deserializer<'de>,
+                {
+                    ddd_rs::__private::serde::Deserialize::deserialize(deserializer).map(Self)
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #generics ddd_rs::domain::Identity for #ident #generics {
+            type Value = #value_ty;
+
+            fn new(value: Self::Value) -> Self {
+                Self(value)
+            }
+
+            fn value(&self) -> &Self::Value {
+                &self.0
+            }
+        }
+
+        impl #generics From<#value_ty> for #ident #generics {
+            fn from(value: #value_ty) -> Self {
+                Self(value)
+            }
+        }
+
+        impl #generics Clone for #ident #generics {
+            fn clone(&self) -> Self {
+                Self(self.0.clone())
+            }
+        }
+
+        impl #generics PartialEq for #ident #generics {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl #generics Eq for #ident #generics {}
+
+        impl #generics PartialOrd for #ident #generics {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl #generics Ord for #ident #generics {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        impl #generics std::hash::Hash for #ident #generics {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl #generics std::fmt::Display for #ident #generics {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl #generics std::str::FromStr for #ident #generics {
+            type Err = <#value_ty as std::str::FromStr>::Err;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Self)
+            }
+        }
+
+        #serde
+    }
+    .into()
+}
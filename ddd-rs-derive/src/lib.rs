@@ -5,33 +5,195 @@
 #![warn(missing_docs)]
 
 mod aggregate_root;
+mod domain_event;
 mod entity;
+mod identity;
+mod state_machine;
 mod value_object;
 
 use proc_macro::TokenStream;
 
 /// Proc macro for deriving the `AggregateRoot` trait.
 ///
+/// Supports structs (named, tuple or unit) and enums, although the attributes below are only
+/// supported for structs.
+///
+/// Fields of non-generic aggregate roots must not be aggregate roots themselves, which should be
+/// referenced by identity (e.g. through an `AggregateRef`) instead. The changes recorded by its
+/// `EntityCollection` fields are cleared through the derived `clear_collection_changes` method.
+///
 /// Use the `#[aggregate_root(domain_events)]` attribute to tag the domain events field of the
-/// aggregate root, which is assumed to be a `Vec`.
+/// aggregate root, which is assumed to be a `Vec`. Its pending domain events are then counted as
+/// the aggregate's pending changes, one per event (or one if there are none, unless event-sourced).
+///
+/// Use the `#[aggregate_root(version)]` attribute to tag the `u64` version field of the aggregate
+/// root, deriving the `Versioned` trait.
+///
+/// Use the `#[aggregate_root(soft_delete)]` attribute to tag the `Option<SystemTime>` deletion
+/// timestamp field of the aggregate root, deriving the `SoftDeletable` trait.
+///
+/// Use the `#[aggregate_root(tenant)]` attribute to tag the tenant identity field of the aggregate
+/// root, deriving the `TenantScoped` trait.
+///
+/// Use the `#[aggregate_root(invariant = path::to::fn)]` attribute on the aggregate root itself,
+/// once for each `fn(&Self) -> bool` invariant, to derive the `Invariants` trait. Without it,
+/// a hand-written `Invariants` implementation is exposed through `as_invariants` instead.
+///
+/// Use the `#[aggregate_root(event_sourced)]` attribute on the aggregate root itself to have the
+/// derived `register_domain_event` method also apply the domain event, through the aggregate's
+/// `EventSourced` implementation. Event-sourced aggregate roots also require a version field.
+///
+/// Use the `#[aggregate_root(builder)]` attribute on the aggregate root itself to derive a builder,
+/// for structs with named fields. Identities tagged with `#[entity(id, generator = ...)]` are
+/// generated (once every required field is set, even if invariants then fail), domain events,
+/// version and soft delete fields are defaulted, fields tagged with
+/// `#[aggregate_root(default)]` are optional and every other field is required. Use the
+/// `#[aggregate_root(created = path::to::fn)]` attribute along with it to register the
+/// `fn(&Self) -> Self::DomainEvent` domain event upon building.
+///
+/// Use the `#[aggregate_root(track_changes)]` attribute on the aggregate root itself, along with a
+/// `ChangeSet` field tagged with `#[aggregate_root(changes)]`, to derive the `ChangeTracked` trait
+/// and a `set_<field>` setter recording the modification of each field, except for the identity,
+/// domain events, version, state, soft delete, tenant and changes fields. The changes field is
+/// defaulted by the builder.
+///
+/// Use the `#[aggregate_root(state)]` attribute to tag a field whose type implements the
+/// `StateMachine` trait, deriving a `transition_to` method for it which also records the change,
+/// if tracked. Use the `#[aggregate_root(state, transition_event = path::to::fn)]` attribute to
+/// also register the `fn(&Self, &From, &To) -> Self::DomainEvent` domain event upon each
+/// transition.
 #[proc_macro_derive(AggregateRoot, attributes(aggregate_root))]
 pub fn derive_aggregate_root(input: TokenStream) -> TokenStream {
     aggregate_root::derive(input)
 }
 
+/// Proc macro for deriving the `DomainEvent` trait.
+///
+/// Supports structs (named, tuple or unit) and enums. Event types default to the name of the struct,
+/// or to `"<Enum>.<Variant>"` for each enum variant, and schema versions default to `1`.
+///
+/// Use the `#[domain_event(name = "...", version = N)]` attribute on a struct or enum variant to
+/// override its event type and schema version. The `version` attribute may also be used on the enum
+/// itself, as the default version of its variants.
+#[proc_macro_derive(DomainEvent, attributes(domain_event))]
+pub fn derive_domain_event(input: TokenStream) -> TokenStream {
+    domain_event::derive(input)
+}
+
 /// Proc macro for deriving the `Entity` trait.
 ///
-/// Use the `#[entity(id)]` attribute to tag the identity (ID) field of the entity.
+/// Use the `#[entity(id)]` attribute to tag the identity (ID) field of the entity. Tagging multiple
+/// fields derives a composite identity, as a tuple of all tagged fields aliased as `<Entity>Id`,
+/// which must be cached in an `IdCache` field tagged with the `#[entity(id_cache)]` attribute.
+///
+/// Supports structs (named, tuple or unit) and enums. Unit structs are singletons, whose identity is
+/// `()`. Enum variants either tag their identity field, or wrap a single entity to which the
+/// identity is delegated.
+///
+/// Use the `#[entity(id, generator = ...)]` attribute to also derive a `generate_id` associated
+/// function, which generates a new identity through the given `IdGenerator`.
+///
+/// Use the `#[entity(hash, ord)]` attribute on the entity itself to also derive `Hash`, `PartialOrd`
+/// and `Ord` from its identity, and `#[entity(copy)]` to derive `Copy` (bounded on the field
+/// types, along with a `Clone` implementation that must be provided separately).
 #[proc_macro_derive(Entity, attributes(entity))]
 pub fn derive_entity(input: TokenStream) -> TokenStream {
     entity::derive(input)
 }
 
+/// Proc macro for deriving the `Identity` trait, for newtype structs.
+///
+/// Also derives `Clone`, `PartialEq`, `Eq`, `PartialOrd`, `Ord`, `Hash`, `Display`, `FromStr` and
+/// `From` (the inner value) from the inner value, as well as `serde`'s `Serialize` and
+/// `Deserialize` with the `serde` feature enabled.
+#[proc_macro_derive(Identity)]
+pub fn derive_identity(input: TokenStream) -> TokenStream {
+    identity::derive(input)
+}
+
+/// Proc macro for deriving the `StateMachine` trait.
+///
+/// Only supports enums. Use the `#[state_machine(to(...))]` attribute on each variant to list the
+/// variants it may transition to. Variants without it are final states.
+#[proc_macro_derive(StateMachine, attributes(state_machine))]
+pub fn derive_state_machine(input: TokenStream) -> TokenStream {
+    state_machine::derive(input)
+}
+
 /// Proc macro for deriving the `ValueObject` trait.
 ///
 /// Use the `#[value_object(eq)]` attribute to tag which fields should be considered as equality
-/// components when comparing value objects.
+/// components when comparing value objects. If no field is tagged, named structs have no equality
+/// components (i.e. all values are equal), while tuple structs and enums consider all of them.
+///
+/// Supports structs (named, tuple or unit) and enums. Newtypes also derive `Deref`, `AsRef` and
+/// `From` (unless validated) for their inner value.
+///
+/// Use the `#[value_object(validate = path::to::fn)]` attribute on the value object itself, or the
+/// `trim`, `lowercase`, `uppercase`, `non_empty`, `min_len`, `max_len`, `min` and `max` attributes
+/// on its fields, to derive a fallible `try_new` constructor and a `TryFrom` implementation.
+///
+/// Use the `#[value_object(hash, ord)]` attribute on the value object itself to also derive `Eq`,
+/// `Hash`, `PartialOrd` and `Ord` from the equality components (enums are ordered by variant
+/// first), and `#[value_object(copy)]` to derive `Copy`, bounded on the field types.
+///
+/// `Copy` is opt-in rather than derived whenever the fields allow it, since bounds on non-generic
+/// field types are checked eagerly (e.g. `String: Copy` fails to compile instead of leaving the
+/// type non-`Copy`), and since dropping `Copy` later is a breaking change for the type's users.
 #[proc_macro_derive(ValueObject, attributes(value_object))]
 pub fn derive_value_object(input: TokenStream) -> TokenStream {
     value_object::derive(input)
 }
+
+/// Members of the given fields, which are either named or positional (i.e. tuple fields).
+fn field_members<'a>(idents: impl IntoIterator<Item = Option<&'a syn::Ident>>) -> Vec<syn::Member> {
+    idents
+        .into_iter()
+        .enumerate()
+        .map(|(i, ident)| match ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        })
+        .collect()
+}
+
+/// Bindings for the given members, when destructuring them in patterns.
+fn field_bindings(prefix: &str, members: &[syn::Member]) -> Vec<syn::Ident> {
+    members
+        .iter()
+        .map(|m| match m {
+            syn::Member::Named(ident) => quote::format_ident!("__{}_{}", prefix, ident),
+            syn::Member::Unnamed(index) => quote::format_ident!("__{}_{}", prefix, index.index),
+        })
+        .collect()
+}
+
+/// `Copy` implementation for the given type, bounded on its field types so that generic types are
+/// only `Copy` when their fields are.
+fn derive_copy<'a>(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    field_ty: impl IntoIterator<Item = &'a syn::Type>,
+) -> proc_macro2::TokenStream {
+    let field_ty = field_ty.into_iter();
+
+    quote::quote! {
+        impl #generics Copy for #ident #generics where #(#field_ty: Copy,)* {}
+    }
+}
+
+/// Struct (or enum variant) expression or pattern, with the given values for each of its members.
+fn fields_expr(
+    path: proc_macro2::TokenStream,
+    style: darling::ast::Style,
+    members: &[syn::Member],
+    values: impl IntoIterator<Item = proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let values = values.into_iter();
+
+    match style {
+        darling::ast::Style::Struct => quote::quote!(#path { #(#members: #values,)* }),
+        darling::ast::Style::Tuple => quote::quote!(#path(#(#values,)*)),
+        darling::ast::Style::Unit => quote::quote!(#path),
+    }
+}
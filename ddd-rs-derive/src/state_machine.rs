@@ -0,0 +1,62 @@
+use darling::FromDeriveInput;
+use proc_macro::TokenStream;
+use quote::quote;
+
+#[derive(darling::FromDeriveInput)]
+#[darling(attributes(state_machine), supports(enum_any))]
+struct StateMachine {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    data: darling::ast::Data<StateMachineVariant, darling::util::Ignored>,
+}
+
+#[derive(darling::FromVariant)]
+#[darling(attributes(state_machine))]
+struct StateMachineVariant {
+    ident: syn::Ident,
+    #[darling(default)]
+    to: darling::util::PathList,
+}
+
+pub fn derive(input: TokenStream) -> TokenStream {
+    let derive_input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    let StateMachine {
+        ident,
+        generics,
+        data,
+    } = match StateMachine::from_derive_input(&derive_input) {
+        Ok(receiver) => receiver,
+        Err(e) => return TokenStream::from(e.write_errors()),
+    };
+
+    let variants = data.take_enum().unwrap();
+
+    let can_transition_to = if variants.is_empty() {
+        quote!(match *self {})
+    } else {
+        let variant_ident = variants.iter().map(|v| &v.ident);
+
+        // Variants without allowed transitions are final states.
+        let allowed = variants.iter().map(|v| {
+            if v.to.is_empty() {
+                quote!(false)
+            } else {
+                let to = v.to.iter();
+
+                quote!(matches!(next, #(Self::#to { .. })|*))
+            }
+        });
+
+        quote!(match self { #(Self::#variant_ident { .. } => #allowed,)* })
+    };
+
+    quote! {
+        impl #generics ddd_rs::domain::StateMachine for #ident #generics {
+            fn can_transition_to(&self, next: &Self) -> bool {
+                #can_transition_to
+            }
+        }
+    }
+    .into()
+}
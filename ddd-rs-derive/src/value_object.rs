@@ -1,23 +1,77 @@
 use darling::FromDeriveInput;
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 
 #[derive(darling::FromDeriveInput)]
-#[darling(attributes(value_object), supports(struct_named))]
+#[darling(attributes(value_object), supports(struct_any, enum_any))]
 struct ValueObject {
     ident: syn::Ident,
     generics: syn::Generics,
-    data: darling::ast::Data<darling::util::Ignored, ValueObjectField>,
+    data: darling::ast::Data<ValueObjectVariant, ValueObjectField>,
+    validate: Option<syn::Path>,
+    hash: Option<HashMarker>,
+    ord: Option<OrdMarker>,
+    copy: Option<CopyMarker>,
 }
 
+#[derive(darling::FromVariant)]
+#[darling(attributes(value_object))]
+struct ValueObjectVariant {
+    ident: syn::Ident,
+    fields: darling::ast::Fields<ValueObjectField>,
+}
+
+#[derive(darling::FromMeta)]
+struct HashMarker;
+
+#[derive(darling::FromMeta)]
+struct OrdMarker;
+
+#[derive(darling::FromMeta)]
+struct CopyMarker;
+
 #[derive(darling::FromMeta)]
 struct EqMarker;
 
+#[derive(darling::FromMeta)]
+struct TrimMarker;
+
+#[derive(darling::FromMeta)]
+struct LowercaseMarker;
+
+#[derive(darling::FromMeta)]
+struct UppercaseMarker;
+
+#[derive(darling::FromMeta)]
+struct NonEmptyMarker;
+
 #[derive(darling::FromField)]
 #[darling(attributes(value_object))]
 struct ValueObjectField {
     ident: Option<syn::Ident>,
+    ty: syn::Type,
     eq: Option<EqMarker>,
+    trim: Option<TrimMarker>,
+    lowercase: Option<LowercaseMarker>,
+    uppercase: Option<UppercaseMarker>,
+    non_empty: Option<NonEmptyMarker>,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    min: Option<syn::Expr>,
+    max: Option<syn::Expr>,
+}
+
+impl ValueObjectField {
+    fn is_validated(&self) -> bool {
+        self.trim.is_some()
+            || self.lowercase.is_some()
+            || self.uppercase.is_some()
+            || self.non_empty.is_some()
+            || self.min_len.is_some()
+            || self.max_len.is_some()
+            || self.min.is_some()
+            || self.max.is_some()
+    }
 }
 
 pub fn derive(input: TokenStream) -> TokenStream {
@@ -27,46 +81,570 @@ pub fn derive(input: TokenStream) -> TokenStream {
         ident,
         generics,
         data,
-        ..
+        validate,
+        hash,
+        ord,
+        copy,
     } = match ValueObject::from_derive_input(&derive_input) {
         Ok(receiver) => receiver,
         Err(e) => return TokenStream::from(e.write_errors()),
     };
 
-    let fields = data.take_struct().unwrap();
+    let copy = copy.map(|_| match &data {
+        darling::ast::Data::Struct(fields) => {
+            crate::derive_copy(&ident, &generics, fields.iter().map(|f| &f.ty))
+        }
+        darling::ast::Data::Enum(variants) => crate::derive_copy(
+            &ident,
+            &generics,
+            variants.iter().flat_map(|v| v.fields.iter().map(|f| &f.ty)),
+        ),
+    });
+
+    let (value_object, components) = match data {
+        darling::ast::Data::Struct(fields) => derive_struct(&ident, &generics, fields, validate),
+        darling::ast::Data::Enum(variants) => {
+            if validate.is_some() {
+                panic!("Validation is not supported for enum value objects");
+            }
+
+            derive_enum(&ident, &generics, variants)
+        }
+    };
+
+    let Components {
+        eq,
+        hash: hash_body,
+        cmp,
+    } = components;
+
+    // `Hash` and `Ord` are only consistent with `PartialEq` if equality is total.
+    let total_eq = (hash.is_some() || ord.is_some())
+        .then(|| quote!(impl #generics Eq for #ident #generics {}));
+
+    let hash = hash.map(|_| {
+        quote! {
+            impl #generics std::hash::Hash for #ident #generics {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    #hash_body
+                }
+            }
+        }
+    });
+
+    let ord = ord.map(|_| {
+        quote! {
+            impl #generics PartialOrd for #ident #generics {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl #generics Ord for #ident #generics {
+                fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    #cmp
+                }
+            }
+        }
+    });
+
+    quote! {
+        #value_object
+
+        impl #generics PartialEq for #ident #generics {
+            fn eq(&self, other: &Self) -> bool {
+                #eq
+            }
+        }
+
+        #total_eq
 
-    derive_value_object(ident, generics, fields)
+        #hash
+
+        #ord
+
+        #copy
+    }
+    .into()
 }
 
-fn derive_value_object(
-    ident: syn::Ident,
-    generics: syn::Generics,
+/// Bodies of the `PartialEq::eq`, `Hash::hash` and `Ord::cmp` implementations, all of which are
+/// derived from the same equality components.
+struct Components {
+    eq: proc_macro2::TokenStream,
+    hash: proc_macro2::TokenStream,
+    cmp: proc_macro2::TokenStream,
+}
+
+fn derive_struct(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
     fields: darling::ast::Fields<ValueObjectField>,
-) -> TokenStream {
-    let fields = fields
-        .into_iter()
-        .map(|f| (f.eq, f.ident.as_ref().map(|ident| quote!(#ident)).unwrap()))
+    validate: Option<syn::Path>,
+) -> (proc_macro2::TokenStream, Components) {
+    let style = fields.style;
+    let fields = fields.into_iter().collect::<Vec<_>>();
+
+    let try_new = (validate.is_some() || fields.iter().any(ValueObjectField::is_validated))
+        .then(|| derive_try_new(ident, generics, style, &fields, validate));
+
+    let newtype = match (style, fields.as_slice()) {
+        (darling::ast::Style::Tuple, [field]) => Some(derive_newtype(
+            ident,
+            generics,
+            &field.ty,
+            try_new.is_none(),
+        )),
+        _ => None,
+    };
+
+    let member = crate::field_members(fields.iter().map(|f| f.ident.as_ref()));
+    let eq_member = member
+        .iter()
+        .zip(eq_components(&fields, style != darling::ast::Style::Struct))
+        .filter_map(|(m, eq)| eq.then_some(m))
         .collect::<Vec<_>>();
 
-    let field = fields.iter().map(|(_, f)| f);
-    let eq_field = fields.iter().filter_map(|(eq, f)| eq.as_ref().map(|_| f));
+    let clone = crate::fields_expr(
+        quote!(Self),
+        style,
+        &member,
+        member.iter().map(|m| quote!(self.#m.clone())),
+    );
 
-    quote! {
+    let value_object = quote! {
         impl #generics ddd_rs::domain::ValueObject for #ident #generics {}
 
         impl #generics Clone for #ident #generics {
             fn clone(&self) -> Self {
-                Self {
-                    #(#field: self.#field.clone(),)*
+                #clone
+            }
+        }
+
+        #try_new
+
+        #newtype
+    };
+
+    let components = Components {
+        eq: quote!(true #( && self.#eq_member == other.#eq_member)*),
+        hash: quote!(#(std::hash::Hash::hash(&self.#eq_member, state);)*),
+        cmp: quote! {
+            std::cmp::Ordering::Equal
+                #(.then_with(|| self.#eq_member.cmp(&other.#eq_member)))*
+        },
+    };
+
+    (value_object, components)
+}
+
+fn derive_enum(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    variants: Vec<ValueObjectVariant>,
+) -> (proc_macro2::TokenStream, Components) {
+    if variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .any(ValueObjectField::is_validated)
+    {
+        panic!("Validation is not supported for enum value objects");
+    }
+
+    let clone_arm = variants.iter().map(|v| {
+        let variant_ident = &v.ident;
+        let member = crate::field_members(v.fields.iter().map(|f| f.ident.as_ref()));
+        let binding = crate::field_bindings("f", &member);
+
+        let pat = crate::fields_expr(
+            quote!(Self::#variant_ident),
+            v.fields.style,
+            &member,
+            binding.iter().map(|b| quote!(#b)),
+        );
+        let clone = crate::fields_expr(
+            quote!(Self::#variant_ident),
+            v.fields.style,
+            &member,
+            binding.iter().map(|b| quote!(#b.clone())),
+        );
+
+        quote!(#pat => #clone,)
+    });
+
+    // Patterns binding the equality components of each variant, as `(pattern, bindings)`.
+    let eq_pat = |prefix: &str| {
+        variants
+            .iter()
+            .map(|v| {
+                let variant_ident = &v.ident;
+                let fields = v.fields.iter().collect::<Vec<_>>();
+                let member = crate::field_members(fields.iter().map(|f| f.ident.as_ref()));
+                let eq = eq_components(fields.iter().copied(), true);
+                let binding = crate::field_bindings(prefix, &member);
+
+                let pat = crate::fields_expr(
+                    quote!(Self::#variant_ident),
+                    v.fields.style,
+                    &member,
+                    binding
+                        .iter()
+                        .zip(&eq)
+                        .map(|(b, eq)| if *eq { quote!(#b) } else { quote!(_) }),
+                );
+
+                let eq_binding = binding
+                    .into_iter()
+                    .zip(&eq)
+                    .filter_map(|(b, eq)| eq.then_some(b))
+                    .collect::<Vec<_>>();
+
+                (pat, eq_binding)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let (l_pat, l_binding) = eq_pat("l").into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+    let (r_pat, r_binding) = eq_pat("r").into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+
+    let value_object = |clone| {
+        quote! {
+            impl #generics ddd_rs::domain::ValueObject for #ident #generics {}
+
+            impl #generics Clone for #ident #generics {
+                fn clone(&self) -> Self {
+                    #clone
+                }
+            }
+        }
+    };
+
+    if variants.is_empty() {
+        let components = Components {
+            eq: quote!(match *self {}),
+            hash: quote!(match *self {}),
+            cmp: quote!(match *self {}),
+        };
+
+        return (value_object(quote!(match *self {})), components);
+    }
+
+    let variant_ident = variants.iter().map(|v| &v.ident);
+    let index = 0..variants.len();
+
+    // Values of different variants are never equal, and are ordered by declaration.
+    let (eq_fallback, cmp_fallback) = (variants.len() > 1)
+        .then(|| {
+            (
+                quote!(_ => false,),
+                quote! {
+                    _ => {
+                        let index = |value: &Self| match value {
+                            #(Self::#variant_ident { .. } => #index,)*
+                        };
+
+                        index(self).cmp(&index(other))
+                    }
+                },
+            )
+        })
+        .unzip();
+
+    let eq_arm = l_pat.iter().zip(&r_pat).zip(l_binding.iter().zip(&r_binding)).map(
+        |((l_pat, r_pat), (l_binding, r_binding))| {
+            quote!((#l_pat, #r_pat) => true #( && #l_binding == #r_binding)*,)
+        },
+    );
+
+    let hash_arm = l_pat.iter().zip(&l_binding).map(
+        |(l_pat, l_binding)| quote!(#l_pat => { #(std::hash::Hash::hash(#l_binding, state);)* }),
+    );
+
+    let cmp_arm = l_pat
+        .iter()
+        .zip(&r_pat)
+        .zip(l_binding.iter().zip(&r_binding))
+        .map(|((l_pat, r_pat), (l_binding, r_binding))| {
+            quote! {
+                (#l_pat, #r_pat) => std::cmp::Ordering::Equal
+                    #(.then_with(|| #l_binding.cmp(#r_binding)))*,
+            }
+        });
+
+    let components = Components {
+        eq: quote!(match (self, other) { #(#eq_arm)* #eq_fallback }),
+        hash: quote! {
+            std::hash::Hash::hash(&std::mem::discriminant(self), state);
+
+            match self { #(#hash_arm)* }
+        },
+        cmp: quote!(match (self, other) { #(#cmp_arm)* #cmp_fallback }),
+    };
+
+    (
+        value_object(quote!(match self { #(#clone_arm)* })),
+        components,
+    )
+}
+
+/// Whether each field is an equality component: either it is tagged with `#[value_object(eq)]` or
+/// none of the fields are and `all_by_default` is set.
+///
+/// Named structs without tagged fields have no equality components, for backwards compatibility,
+/// while tuple structs and enums compare all of their fields.
+fn eq_components<'a>(
+    fields: impl IntoIterator<Item = &'a ValueObjectField>,
+    all_by_default: bool,
+) -> Vec<bool> {
+    let eq = fields
+        .into_iter()
+        .map(|f| f.eq.is_some())
+        .collect::<Vec<_>>();
+
+    if eq.contains(&true) || !all_by_default {
+        eq
+    } else {
+        vec![true; eq.len()]
+    }
+}
+
+fn derive_newtype(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    ty: &syn::Type,
+    from: bool,
+) -> proc_macro2::TokenStream {
+    // Validated newtypes are converted through `TryFrom` instead.
+    let from = from.then(|| {
+        quote! {
+            impl #generics From<#ty> for #ident #generics {
+                fn from(value: #ty) -> Self {
+                    Self(value)
                 }
             }
         }
+    });
 
-        impl #generics PartialEq for #ident #generics {
-            fn eq(&self, other: &Self) -> bool {
-                true #( && self.#eq_field == other.#eq_field)*
+    quote! {
+        impl #generics std::ops::Deref for #ident #generics {
+            type Target = #ty;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
             }
         }
+
+        impl #generics AsRef<#ty> for #ident #generics {
+            fn as_ref(&self) -> &#ty {
+                &self.0
+            }
+        }
+
+        #from
+    }
+}
+
+fn derive_try_new(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    style: darling::ast::Style,
+    fields: &[ValueObjectField],
+    validate: Option<syn::Path>,
+) -> proc_macro2::TokenStream {
+    let member = crate::field_members(fields.iter().map(|f| f.ident.as_ref()));
+
+    // Tuple fields are named after their position, except for newtypes, whose violations apply
+    // to the value object as a whole.
+    let (param, name) = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match (&f.ident, fields.len()) {
+            (Some(ident), _) => (ident.clone(), Some(ident.to_string())),
+            (None, 1) => (quote::format_ident!("value"), None),
+            (None, _) => (quote::format_ident!("value_{}", i), Some(i.to_string())),
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    let field_ty = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let normalize = fields
+        .iter()
+        .zip(&param)
+        .map(|(f, param)| derive_normalize(f, param));
+    let check = fields
+        .iter()
+        .zip(&param)
+        .zip(&name)
+        .map(|((f, param), name)| derive_check(f, param, name.as_deref()));
+
+    let value_object = crate::fields_expr(
+        quote!(Self),
+        style,
+        &member,
+        param.iter().map(|p| quote!(#p)),
+    );
+
+    let validate = validate.map(|validate| {
+        quote! {
+            if let Err(e) = #validate(&value_object) {
+                violations.extend(e);
+            }
+        }
+    });
+
+    let try_from = match fields {
+        [_] => quote! {
+            impl #generics TryFrom<#(#field_ty)*> for #ident #generics {
+                type Error = ddd_rs::domain::ValidationError;
+
+                fn try_from(value: #(#field_ty)*) -> Result<Self, Self::Error> {
+                    Self::try_new(value)
+                }
+            }
+        },
+        _ => quote! {
+            impl #generics TryFrom<(#(#field_ty,)*)> for #ident #generics {
+                type Error = ddd_rs::domain::ValidationError;
+
+                fn try_from((#(#param,)*): (#(#field_ty,)*)) -> Result<Self, Self::Error> {
+                    Self::try_new(#(#param,)*)
+                }
+            }
+        },
+    };
+
+    quote! {
+        impl #generics #ident #generics {
+            /// Creates a new value object, normalizing and validating its fields.
+            #[allow(clippy::too_many_arguments)]
+            pub fn try_new(
+                #(#param: #field_ty,)*
+            ) -> Result<Self, ddd_rs::domain::ValidationError> {
+                #(#normalize)*
+
+                let mut violations = Vec::<ddd_rs::domain::Violation>::new();
+
+                #(#check)*
+
+                let value_object = #value_object;
+
+                #validate
+
+                if violations.is_empty() {
+                    Ok(value_object)
+                } else {
+                    Err(ddd_rs::domain::ValidationError::new(violations))
+                }
+            }
+        }
+
+        #try_from
+    }
+}
+
+fn derive_normalize(field: &ValueObjectField, ident: &syn::Ident) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+
+    let trim = field.trim.as_ref().map(|_| {
+        quote! {
+            let #ident: #ty = #ident.trim().into();
+        }
+    });
+    let lowercase = field.lowercase.as_ref().map(|_| {
+        quote! {
+            let #ident: #ty = #ident.to_lowercase().into();
+        }
+    });
+    let uppercase = field.uppercase.as_ref().map(|_| {
+        quote! {
+            let #ident: #ty = #ident.to_uppercase().into();
+        }
+    });
+
+    quote! {
+        #trim
+        #lowercase
+        #uppercase
+    }
+}
+
+fn derive_check(
+    field: &ValueObjectField,
+    ident: &syn::Ident,
+    name: Option<&str>,
+) -> proc_macro2::TokenStream {
+    let violation = |condition: proc_macro2::TokenStream, rule: &str, message: String| {
+        let violation = match name {
+            Some(name) => quote!(ddd_rs::domain::Violation::for_field(#name, #rule, #message)),
+            None => quote!(ddd_rs::domain::Violation::new(#rule, #message)),
+        };
+
+        quote! {
+            if #condition {
+                violations.push(#violation);
+            }
+        }
+    };
+
+    let non_empty = field.non_empty.as_ref().map(|_| {
+        violation(
+            quote!(#ident.is_empty()),
+            "non_empty",
+            "must not be empty".to_string(),
+        )
+    });
+    // Strings are measured in characters, rather than bytes.
+    let len = if is_string(&field.ty) {
+        quote!(#ident.chars().count())
+    } else {
+        quote!(#ident.len())
+    };
+
+    let min_len = field.min_len.map(|min_len| {
+        violation(
+            quote!(#len < #min_len),
+            "min_len",
+            format!("must have a length of at least {}", min_len),
+        )
+    });
+    let max_len = field.max_len.map(|max_len| {
+        violation(
+            quote!(#len > #max_len),
+            "max_len",
+            format!("must have a length of at most {}", max_len),
+        )
+    });
+    let min = field.min.as_ref().map(|min| {
+        violation(
+            quote!(#ident < #min),
+            "min",
+            format!("must be at least {}", min.to_token_stream()),
+        )
+    });
+    let max = field.max.as_ref().map(|max| {
+        violation(
+            quote!(#ident > #max),
+            "max",
+            format!("must be at most {}", max.to_token_stream()),
+        )
+    });
+
+    quote! {
+        #non_empty
+        #min_len
+        #max_len
+        #min
+        #max
+    }
+}
+
+/// Whether the type is a `String` or a `str` (possibly borrowed).
+fn is_string(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { path, .. }) => path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "String" || s.ident == "str"),
+        syn::Type::Reference(syn::TypeReference { elem, .. }) => is_string(elem),
+        _ => false,
     }
-    .into()
 }
@@ -0,0 +1,72 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+/// Trait for representing a **Clock**.
+///
+/// Injecting a clock into services, instead of calling [SystemTime::now] directly, keeps their
+/// timestamps deterministic under test.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// use ddd_rs::application::{Clock, FixedClock};
+///
+/// let clock = FixedClock::new(SystemTime::UNIX_EPOCH);
+///
+/// assert_eq!(clock.now(), SystemTime::UNIX_EPOCH);
+///
+/// clock.advance(Duration::from_secs(60));
+///
+/// assert_eq!(clock.now(), SystemTime::UNIX_EPOCH + Duration::from_secs(60));
+/// ```
+pub trait Clock: Send + Sync {
+    /// Current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// A [Clock] backed by the system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A [Clock] that only moves when told to.
+///
+/// See [Clock] for an example.
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<SystemTime>,
+}
+
+impl FixedClock {
+    /// Creates a new [FixedClock], set to the given time.
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Sets the clock to the given time.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Moves the clock forward by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+}
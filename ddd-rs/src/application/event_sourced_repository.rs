@@ -0,0 +1,399 @@
+use std::sync::Arc;
+
+use crate::domain::{Entity, EventSourced, Specification};
+
+use super::{
+    ConcurrencyError, EventSerializer, EventStore, ReadRepository, Repository, RepositoryError,
+    SnapshotPolicy, SnapshotStore, UpcasterRegistry,
+};
+
+/// A [Repository] of [EventSourced] aggregates, persisted as streams of domain events in an
+/// [EventStore], one per aggregate.
+///
+/// Streams are identified as `<stream name>-<aggregate ID>`, so that aggregates of different types
+/// may share the same [EventStore]. The stream name defaults to the aggregate's type name, which is
+/// not guaranteed to be stable across compiler versions nor refactorings, hence persistent event
+/// stores should set it through [with_stream_name](EventSourcedRepository::with_stream_name).
+///
+/// Aggregates are loaded from their latest snapshot, if a [SnapshotStore] is configured, by
+/// replaying only the events that follow it. Snapshots are taken according to the
+/// [SnapshotPolicy], or on demand through [snapshot](EventSourcedRepository::snapshot). Failing
+/// to take a snapshot according to the policy does not fail persisting the aggregate.
+///
+/// Aggregates are added as new streams, hence [add](Repository::add) fails with
+/// [RepositoryError::AlreadyExists] if the aggregate's stream already has events, while
+/// [update](Repository::update) fails with [RepositoryError::NotFound] if it has none.
+///
+/// Since this repository takes the aggregate's domain events in order to persist them, wrapping it
+/// in a [RepositoryEx](super::RepositoryEx) would leave none of them to be handled.
+///
+/// Event streams cannot be listed nor deleted, hence [list](ReadRepository::list),
+/// [count](ReadRepository::count), [find](ReadRepository::find) (along with the other
+/// specification-based queries) and [delete](Repository::delete) always fail with
+/// [RepositoryError::Unsupported]. Model deletion as a domain event instead.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ddd_rs::{
+///     application::{
+///         ConcurrencyError, EventSerializer, EventSourcedRepository, ReadRepository, Repository,
+///         RepositoryError, SerializedEvent, SnapshotPolicy, SnapshotStore,
+///     },
+///     domain::{AggregateRootEx, EventSourced, Versioned},
+///     infrastructure::{InMemoryEventStore, InMemorySnapshotStore},
+/// };
+/// use serde_json::Value;
+///
+/// #[derive(ddd_rs::DomainEvent, serde::Serialize, serde::Deserialize, Clone, Debug)]
+/// enum AccountEvent {
+///     Opened { id: u32 },
+///     Deposited { amount: u64 },
+///     Withdrawn { amount: u64 },
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone, Debug, Default)]
+/// #[aggregate_root(event_sourced)]
+/// struct Account {
+///     #[entity(id)]
+///     id: u32,
+///     balance: u64,
+///     #[aggregate_root(version)]
+///     version: u64,
+///     #[aggregate_root(domain_events)]
+///     domain_events: Vec<AccountEvent>,
+/// }
+///
+/// impl EventSourced for Account {
+///     fn apply(&mut self, event: &AccountEvent) {
+///         match event {
+///             AccountEvent::Opened { id } => self.id = *id,
+///             AccountEvent::Deposited { amount } => self.balance += amount,
+///             AccountEvent::Withdrawn { amount } => self.balance -= amount,
+///         }
+///     }
+/// }
+///
+/// struct JsonSerializer;
+///
+/// impl EventSerializer<AccountEvent, Value> for JsonSerializer {
+///     fn serialize(&self, event: &AccountEvent) -> ddd_rs::Result<SerializedEvent<Value>> {
+///         Ok(SerializedEvent::of(event, serde_json::to_value(event)?))
+///     }
+///
+///     fn deserialize(&self, event: SerializedEvent<Value>) -> ddd_rs::Result<AccountEvent> {
+///         Ok(serde_json::from_value(event.payload)?)
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let snapshot_store = Arc::new(InMemorySnapshotStore::new());
+///
+/// let repository = EventSourcedRepository::new(
+///     Arc::new(InMemoryEventStore::new()),
+///     Arc::new(JsonSerializer),
+/// )
+/// .with_stream_name("account")
+/// .with_snapshots(snapshot_store.clone(), SnapshotPolicy::EveryNEvents(3));
+///
+/// let mut account = Account::default();
+///
+/// account.register_domain_event(AccountEvent::Opened { id: 42 });
+///
+/// let mut account = repository.add(account).await.unwrap();
+///
+/// assert_eq!(account.version(), 1);
+/// assert!(snapshot_store.load(42).await.unwrap().is_none());
+///
+/// account.register_domain_event(AccountEvent::Deposited { amount: 100 });
+/// account.register_domain_event(AccountEvent::Deposited { amount: 50 });
+///
+/// let mut account = repository.update(account).await.unwrap();
+///
+/// // A snapshot is taken once the aggregate reaches its third event.
+/// let snapshot = snapshot_store.load(42).await.unwrap().unwrap();
+///
+/// assert_eq!(snapshot.version(), 3);
+/// assert_eq!(snapshot.balance, 150);
+///
+/// account.register_domain_event(AccountEvent::Withdrawn { amount: 30 });
+///
+/// let account = repository.update(account).await.unwrap();
+///
+/// let loaded = repository.get_by_id(42).await.unwrap().unwrap();
+///
+/// assert_eq!(loaded.version(), 4);
+/// assert_eq!(loaded.balance, 120);
+///
+/// // Only the events after the snapshot are replayed, which is shown by tampering with it.
+/// snapshot_store.save(Account { balance: 1000, ..snapshot }).await.unwrap();
+///
+/// assert_eq!(repository.get_by_id(42).await.unwrap().unwrap().balance, 970);
+///
+/// // Snapshots may also be taken on demand, as long as there are no pending domain events.
+/// repository.snapshot(&account).await.unwrap();
+///
+/// assert_eq!(snapshot_store.load(42).await.unwrap().unwrap().version(), 4);
+///
+/// let mut pending = account.clone();
+///
+/// pending.register_domain_event(AccountEvent::Deposited { amount: 1 });
+///
+/// assert!(repository.snapshot(&pending).await.is_err());
+///
+/// // Streams are only created by adding aggregates, and only once.
+/// let mut duplicate = Account::default();
+///
+/// duplicate.register_domain_event(AccountEvent::Opened { id: 42 });
+///
+/// let error = repository.add(duplicate).await.unwrap_err();
+///
+/// assert!(matches!(error, RepositoryError::AlreadyExists));
+///
+/// let mut unknown = Account::default();
+///
+/// unknown.register_domain_event(AccountEvent::Opened { id: 7 });
+///
+/// let error = repository.update(unknown).await.unwrap_err();
+///
+/// assert!(matches!(error, RepositoryError::NotFound));
+///
+/// // Stale aggregates are rejected.
+/// let mut stale = Account { version: 3, ..account };
+///
+/// stale.register_domain_event(AccountEvent::Deposited { amount: 1 });
+///
+/// let error = repository.update(stale).await.unwrap_err();
+///
+/// assert!(matches!(
+///     error,
+///     RepositoryError::Concurrency(ConcurrencyError { expected: 3, actual: 4 })
+/// ));
+/// # })
+/// ```
+pub struct EventSourcedRepository<T: EventSourced, P> {
+    event_store: Arc<dyn EventStore<P>>,
+    serializer: Arc<dyn EventSerializer<T::DomainEvent, P>>,
+    upcasters: UpcasterRegistry<P>,
+    snapshots: Option<(Arc<dyn SnapshotStore<T>>, SnapshotPolicy)>,
+    stream_name: String,
+}
+
+impl<T: EventSourced, P: Send> EventSourcedRepository<T, P> {
+    /// Creates a new [EventSourcedRepository], without upcasters nor snapshots, naming its streams
+    /// after the aggregate's type.
+    pub fn new(
+        event_store: Arc<dyn EventStore<P>>,
+        serializer: Arc<dyn EventSerializer<T::DomainEvent, P>>,
+    ) -> Self {
+        Self {
+            event_store,
+            serializer,
+            upcasters: UpcasterRegistry::new(),
+            snapshots: None,
+            stream_name: std::any::type_name::<T>().to_string(),
+        }
+    }
+
+    /// Sets the name of the aggregates' event streams, which are identified as
+    /// `<stream name>-<aggregate ID>`.
+    pub fn with_stream_name(mut self, stream_name: impl ToString) -> Self {
+        self.stream_name = stream_name.to_string();
+        self
+    }
+
+    /// Sets the [UpcasterRegistry] used for upcasting events before deserializing them.
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry<P>) -> Self {
+        self.upcasters = upcasters;
+        self
+    }
+
+    /// Sets the [SnapshotStore] used for loading aggregates, and the [SnapshotPolicy] for taking
+    /// snapshots upon persisting them.
+    pub fn with_snapshots(
+        mut self,
+        snapshot_store: Arc<dyn SnapshotStore<T>>,
+        snapshot_policy: SnapshotPolicy,
+    ) -> Self {
+        self.snapshots = Some((snapshot_store, snapshot_policy));
+        self
+    }
+
+    /// Takes a snapshot of the aggregate, regardless of the [SnapshotPolicy].
+    ///
+    /// This fails if the aggregate has pending domain events, since they are not part of its
+    /// history yet.
+    pub async fn snapshot(&self, aggregate: &T) -> crate::Result<()>
+    where
+        T: Clone,
+    {
+        let (snapshot_store, _) = self
+            .snapshots
+            .as_ref()
+            .ok_or("Snapshots are not configured for this repository")?;
+
+        let mut snapshot = aggregate.clone();
+
+        if !snapshot.take_domain_events().is_empty() {
+            return Err("Aggregates with pending domain events cannot be snapshotted".into());
+        }
+
+        snapshot_store.save(snapshot).await
+    }
+
+    async fn append(
+        &self,
+        mut entity: T,
+        expected_version: u64,
+    ) -> crate::Result<T, RepositoryError>
+    where
+        T: Clone,
+        <T as Entity>::Id: std::fmt::Display,
+    {
+        if let Some(invariants) = entity.as_invariants() {
+            invariants.check_invariants()?;
+        }
+
+        let events = entity
+            .take_domain_events()
+            .iter()
+            .map(|event| self.serializer.serialize(event))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let version = self
+            .event_store
+            .append(&self.stream_id(entity.id()), expected_version, events)
+            .await?;
+
+        entity.set_version(version);
+
+        if let Some(change_tracked) = entity.as_change_tracked_mut() {
+            change_tracked.clear_changes();
+        }
+
+        entity.clear_collection_changes();
+
+        // Snapshots are best-effort, since the events are already persisted: failing to take one
+        // only slows down loading the aggregate.
+        if let Some((snapshot_store, snapshot_policy)) = &self.snapshots {
+            if snapshot_policy.should_snapshot(expected_version, version) {
+                snapshot_store.save(entity.clone()).await.ok();
+            }
+        }
+
+        Ok(entity)
+    }
+
+    fn stream_id(&self, id: &<T as Entity>::Id) -> String
+    where
+        <T as Entity>::Id: std::fmt::Display,
+    {
+        format!("{}-{}", self.stream_name, id)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, P> ReadRepository<T> for EventSourcedRepository<T, P>
+where
+    T: EventSourced + Clone + Default,
+    <T as Entity>::Id: std::fmt::Display,
+    P: Send + Sync,
+{
+    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>, RepositoryError> {
+        let snapshot = match &self.snapshots {
+            Some((snapshot_store, _)) => snapshot_store.load(id.clone()).await?,
+            None => None,
+        };
+
+        let is_snapshot = snapshot.is_some();
+        let mut aggregate = snapshot.unwrap_or_default();
+
+        let events = self
+            .event_store
+            .load(&self.stream_id(&id), aggregate.version())
+            .await?;
+
+        if !is_snapshot && events.is_empty() {
+            return Ok(None);
+        }
+
+        let events = events
+            .into_iter()
+            .map(|event| self.serializer.deserialize(self.upcasters.upcast(event)?))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        aggregate.replay(events);
+
+        Ok(Some(aggregate))
+    }
+
+    async fn list(&self, _skip: usize, _take: usize) -> crate::Result<Vec<T>, RepositoryError> {
+        Err(RepositoryError::Unsupported(
+            "Event-sourced repositories cannot list aggregates",
+        ))
+    }
+
+    async fn count(&self) -> crate::Result<usize, RepositoryError> {
+        Err(RepositoryError::Unsupported(
+            "Event-sourced repositories cannot count aggregates",
+        ))
+    }
+
+    async fn find(
+        &self,
+        _specification: &dyn Specification<T>,
+    ) -> crate::Result<Vec<T>, RepositoryError> {
+        Err(RepositoryError::Unsupported(
+            "Event-sourced repositories cannot find aggregates",
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, P> Repository<T> for EventSourcedRepository<T, P>
+where
+    T: EventSourced + Clone + Default,
+    <T as Entity>::Id: std::fmt::Display,
+    P: Send + Sync,
+{
+    async fn add(&self, entity: T) -> crate::Result<T, RepositoryError> {
+        // New aggregates are only appended to empty streams, hence a conflict means that the
+        // stream already exists.
+        self.append(entity, 0).await.map_err(|e| match e {
+            RepositoryError::Concurrency(_) => RepositoryError::AlreadyExists,
+            e => e,
+        })
+    }
+
+    async fn update(&self, entity: T) -> crate::Result<T, RepositoryError> {
+        let expected_version = entity.version();
+
+        // Appending at version `0` would create the stream, which must already exist.
+        if expected_version == 0
+            && self
+                .event_store
+                .load(&self.stream_id(entity.id()), 0)
+                .await?
+                .is_empty()
+        {
+            return Err(RepositoryError::NotFound);
+        }
+
+        self.append(entity, expected_version)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::Concurrency(ConcurrencyError { actual: 0, .. }) => {
+                    RepositoryError::NotFound
+                }
+                e => e,
+            })
+    }
+
+    async fn delete(&self, _entity: T) -> crate::Result<(), RepositoryError> {
+        Err(RepositoryError::Unsupported(
+            "Event-sourced repositories cannot delete aggregates",
+        ))
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use crate::domain::DomainEvent;
+
+/// A **Domain Event** serialized for persistence, along with its event type and schema version.
+///
+/// The payload type `P` depends on the serialization format, e.g. `serde_json::Value` or `Vec<u8>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializedEvent<P> {
+    /// Event type of the serialized event.
+    pub event_type: String,
+    /// Schema version of the serialized event.
+    pub schema_version: u32,
+    /// Serialized event.
+    pub payload: P,
+}
+
+impl<P> SerializedEvent<P> {
+    /// Creates a new [SerializedEvent].
+    pub fn new(event_type: impl ToString, schema_version: u32, payload: P) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            schema_version,
+            payload,
+        }
+    }
+
+    /// Creates a new [SerializedEvent] from the given payload, with the event type and schema
+    /// version of the [DomainEvent] it was serialized from.
+    pub fn of(event: &impl DomainEvent, payload: P) -> Self {
+        Self::new(event.event_type(), event.schema_version(), payload)
+    }
+}
+
+/// Trait for representing an **Event Store**, which persists streams of [SerializedEvent]s.
+///
+/// Each stream holds the history of a single aggregate, whose version is the number of events in
+/// the stream.
+///
+/// # Examples
+///
+/// This example uses the [InMemoryEventStore](crate::infrastructure::InMemoryEventStore), which
+/// is a sample implementation of this trait, along with an [UpcasterRegistry] for loading events
+/// persisted with older schema versions:
+///
+/// ```
+/// use ddd_rs::{
+///     application::{ConcurrencyError, EventStore, SerializedEvent, UpcasterRegistry},
+///     infrastructure::InMemoryEventStore,
+/// };
+/// use serde_json::{json, Value};
+///
+/// // Version 1 of `Deposited` had no currency, and version 2 had its amount in whole units.
+/// #[derive(ddd_rs::DomainEvent, serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+/// #[domain_event(version = 3)]
+/// enum AccountEvent {
+///     Deposited { amount_in_cents: u64, currency: String },
+///     Withdrawn { amount_in_cents: u64 },
+/// }
+///
+/// fn serialize(event: &AccountEvent) -> SerializedEvent<Value> {
+///     SerializedEvent::of(event, serde_json::to_value(event).unwrap())
+/// }
+///
+/// let upcasters = UpcasterRegistry::new()
+///     .with_upcaster("AccountEvent.Deposited", 1, |mut payload: Value| {
+///         payload["Deposited"]["currency"] = json!("USD");
+///
+///         Ok(payload)
+///     })
+///     .with_upcaster("AccountEvent.Deposited", 2, |payload: Value| {
+///         let deposited = &payload["Deposited"];
+///
+///         Ok(json!({
+///             "Deposited": {
+///                 "amount_in_cents": deposited["amount"].as_u64().ok_or("Missing amount")? * 100,
+///                 "currency": deposited["currency"],
+///             }
+///         }))
+///     });
+///
+/// # tokio_test::block_on(async {
+/// let event_store = InMemoryEventStore::new();
+///
+/// // Events persisted long ago, with older schema versions.
+/// let history = vec![
+///     SerializedEvent::new(
+///         "AccountEvent.Deposited",
+///         1,
+///         json!({ "Deposited": { "amount": 10 } }),
+///     ),
+///     SerializedEvent::new(
+///         "AccountEvent.Deposited",
+///         2,
+///         json!({ "Deposited": { "amount": 5, "currency": "EUR" } }),
+///     ),
+/// ];
+///
+/// assert_eq!(event_store.append("account-1", 0, history).await.unwrap(), 2);
+///
+/// // Appending is only allowed at the current version of the stream.
+/// let withdrawn = AccountEvent::Withdrawn { amount_in_cents: 250 };
+///
+/// let error = event_store.append("account-1", 1, vec![serialize(&withdrawn)]).await.unwrap_err();
+///
+/// assert_eq!(
+///     error.downcast_ref::<ConcurrencyError>(),
+///     Some(&ConcurrencyError { expected: 1, actual: 2 })
+/// );
+///
+/// assert_eq!(event_store.append("account-1", 2, vec![serialize(&withdrawn)]).await.unwrap(), 3);
+///
+/// // Old events are upcast to the latest schema version before being deserialized.
+/// let events = event_store
+///     .load("account-1", 0)
+///     .await
+///     .unwrap()
+///     .into_iter()
+///     .map(|event| upcasters.upcast(event))
+///     .map(|event| serde_json::from_value(event.unwrap().payload).unwrap())
+///     .collect::<Vec<AccountEvent>>();
+///
+/// assert_eq!(
+///     events,
+///     [
+///         AccountEvent::Deposited { amount_in_cents: 1000, currency: "USD".to_string() },
+///         AccountEvent::Deposited { amount_in_cents: 500, currency: "EUR".to_string() },
+///         AccountEvent::Withdrawn { amount_in_cents: 250 },
+///     ]
+/// );
+///
+/// // Streams may also be loaded from a given version onwards.
+/// assert_eq!(event_store.load("account-1", 2).await.unwrap().len(), 1);
+/// # })
+/// ```
+#[async_trait::async_trait]
+pub trait EventStore<P: Send>: Send + Sync {
+    /// Appends the given events to a stream, returning its new version.
+    ///
+    /// This should fail with a [ConcurrencyError](super::ConcurrencyError) if the current version of
+    /// the stream differs from the expected one.
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: u64,
+        events: Vec<SerializedEvent<P>>,
+    ) -> crate::Result<u64>;
+
+    /// Loads the events of a stream, skipping the first `from_version` ones.
+    async fn load(
+        &self,
+        stream_id: &str,
+        from_version: u64,
+    ) -> crate::Result<Vec<SerializedEvent<P>>>;
+}
+
+/// Trait for representing an **Event Serializer**, which converts domain events to and from
+/// [SerializedEvent]s.
+///
+/// See [EventSourcedRepository](super::EventSourcedRepository) for an example.
+pub trait EventSerializer<E, P>: Send + Sync {
+    /// Serializes the domain event.
+    fn serialize(&self, event: &E) -> crate::Result<SerializedEvent<P>>;
+
+    /// Deserializes the domain event, which was already upcast to its latest schema version.
+    fn deserialize(&self, event: SerializedEvent<P>) -> crate::Result<E>;
+}
+
+type Upcaster<P> = Box<dyn Fn(P) -> crate::Result<P> + Send + Sync>;
+
+/// Registry of **Upcasters**, which transform [SerializedEvent]s from a schema version to the next
+/// one, so that old events can be deserialized into the latest version of their type.
+///
+/// Upcasters are chained, so an event is transformed by every upcaster registered for its event
+/// type, from its schema version onwards.
+///
+/// See [EventStore] for an example.
+pub struct UpcasterRegistry<P> {
+    upcasters: HashMap<(String, u32), Upcaster<P>>,
+}
+
+impl<P> UpcasterRegistry<P> {
+    /// Creates a new, empty [UpcasterRegistry].
+    pub fn new() -> Self {
+        Self {
+            upcasters: HashMap::new(),
+        }
+    }
+
+    /// Registers an upcaster of the given event type, from the given schema version to the next
+    /// one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an upcaster is already registered for the same event type and schema version.
+    pub fn with_upcaster(
+        mut self,
+        event_type: impl ToString,
+        from_version: u32,
+        upcaster: impl Fn(P) -> crate::Result<P> + Send + Sync + 'static,
+    ) -> Self {
+        let key = (event_type.to_string(), from_version);
+
+        if self.upcasters.contains_key(&key) {
+            panic!(
+                "Upcaster of `{}` from version {} is already registered",
+                key.0, key.1
+            );
+        }
+
+        self.upcasters.insert(key, Box::new(upcaster));
+        self
+    }
+
+    /// Upcasts the given event to the latest schema version known by the registry.
+    pub fn upcast(&self, mut event: SerializedEvent<P>) -> crate::Result<SerializedEvent<P>> {
+        while let Some(upcaster) = self
+            .upcasters
+            .get(&(event.event_type.clone(), event.schema_version))
+        {
+            event.payload = upcaster(event.payload)?;
+            event.schema_version += 1;
+        }
+
+        Ok(event)
+    }
+}
+
+impl<P> Default for UpcasterRegistry<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
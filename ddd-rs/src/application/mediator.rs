@@ -0,0 +1,223 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::BoxError;
+
+use super::{Request, RequestHandler};
+
+/// A **Mediator**, dispatching each [Request] to the [RequestHandler] registered for its type.
+///
+/// Callers then depend on the [Mediator] alone, rather than on every service handling the
+/// [Commands](super::Command) and [Queries](super::Query) they issue.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use ddd_rs::application::{Command, CommandHandler, Mediator, MediatorError, Query, QueryHandler};
+///
+/// #[derive(Debug)]
+/// struct CounterError(&'static str);
+///
+/// impl std::fmt::Display for CounterError {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         self.0.fmt(f)
+///     }
+/// }
+///
+/// impl std::error::Error for CounterError {}
+///
+/// #[derive(Default)]
+/// struct CounterService {
+///     count: Mutex<u32>,
+/// }
+///
+/// struct IncrementCommand {
+///     by: u32,
+/// }
+///
+/// impl Command for IncrementCommand {}
+///
+/// #[async_trait::async_trait]
+/// impl CommandHandler<IncrementCommand> for CounterService {
+///     type Error = CounterError;
+///
+///     async fn handle(&self, command: IncrementCommand) -> Result<(), Self::Error> {
+///         let mut count = self.count.lock().unwrap();
+///
+///         *count = count.checked_add(command.by).ok_or(CounterError("Overflow"))?;
+///
+///         Ok(())
+///     }
+/// }
+///
+/// struct GetCountQuery;
+///
+/// impl Query for GetCountQuery {
+///     type Response = u32;
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl QueryHandler<GetCountQuery> for CounterService {
+///     type Error = CounterError;
+///
+///     async fn handle(&self, _query: GetCountQuery) -> Result<u32, Self::Error> {
+///         Ok(*self.count.lock().unwrap())
+///     }
+/// }
+///
+/// struct ResetCommand;
+///
+/// impl Command for ResetCommand {}
+///
+/// // Register the handlers, which may be shared by several request types.
+///
+/// let counter = Arc::new(CounterService::default());
+///
+/// let mut mediator = Mediator::new();
+///
+/// mediator.register::<IncrementCommand, _>(counter.clone()).unwrap();
+/// mediator.register::<GetCountQuery, _>(counter.clone()).unwrap();
+///
+/// // Each request type has a single handler.
+/// assert!(matches!(
+///     mediator.register::<GetCountQuery, _>(counter.clone()),
+///     Err(MediatorError::AlreadyRegistered { .. })
+/// ));
+///
+/// # tokio_test::block_on(async {
+/// mediator.send(IncrementCommand { by: 2 }).await.unwrap();
+/// mediator.send(IncrementCommand { by: 3 }).await.unwrap();
+///
+/// assert_eq!(mediator.send(GetCountQuery).await.unwrap(), 5);
+///
+/// // Handler errors are forwarded.
+/// let error = mediator.send(IncrementCommand { by: u32::MAX }).await.unwrap_err();
+///
+/// assert!(matches!(error, MediatorError::Handler(_)));
+/// assert_eq!(error.to_string(), "Request handler error: Overflow");
+///
+/// // Sending a request without a registered handler fails.
+/// assert!(matches!(
+///     mediator.send(ResetCommand).await,
+///     Err(MediatorError::HandlerNotFound { .. })
+/// ));
+/// # })
+/// ```
+#[derive(Default)]
+pub struct Mediator {
+    handlers: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Mediator {
+    /// Creates a new [Mediator], without any registered handler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the [RequestHandler] for the given [Request] type.
+    ///
+    /// Fails with [MediatorError::AlreadyRegistered] if the request type already has a handler.
+    pub fn register<R, H>(&mut self, handler: Arc<H>) -> crate::Result<(), MediatorError>
+    where
+        R: Request + 'static,
+        H: RequestHandler<R> + 'static,
+        H::Error: Send + Sync + 'static,
+    {
+        let type_id = TypeId::of::<R>();
+
+        if self.handlers.contains_key(&type_id) {
+            return Err(MediatorError::AlreadyRegistered {
+                request: std::any::type_name::<R>(),
+            });
+        }
+
+        let handler: Arc<dyn ErasedRequestHandler<R>> = handler;
+
+        self.handlers.insert(type_id, Box::new(handler));
+
+        Ok(())
+    }
+
+    /// Sends the [Request] to its registered [RequestHandler], returning its
+    /// [Response](Request::Response).
+    ///
+    /// Fails with [MediatorError::HandlerNotFound] if the request type has no handler, or
+    /// [MediatorError::Handler] if the handler fails.
+    pub async fn send<R: Request + 'static>(
+        &self,
+        request: R,
+    ) -> crate::Result<R::Response, MediatorError> {
+        let handler = self
+            .handlers
+            .get(&TypeId::of::<R>())
+            .and_then(|h| h.downcast_ref::<Arc<dyn ErasedRequestHandler<R>>>())
+            .ok_or(MediatorError::HandlerNotFound {
+                request: std::any::type_name::<R>(),
+            })?;
+
+        handler
+            .handle(request)
+            .await
+            .map_err(MediatorError::Handler)
+    }
+}
+
+/// [RequestHandler] with its error type erased, so that handlers of the same [Request] type may be
+/// stored alike.
+#[async_trait::async_trait]
+trait ErasedRequestHandler<R: Request>: Send + Sync {
+    async fn handle(&self, request: R) -> crate::Result<R::Response>;
+}
+
+#[async_trait::async_trait]
+impl<R, H> ErasedRequestHandler<R> for H
+where
+    R: Request + 'static,
+    H: RequestHandler<R>,
+    H::Error: Send + Sync + 'static,
+{
+    async fn handle(&self, request: R) -> crate::Result<R::Response> {
+        Ok(RequestHandler::handle(self, request).await?)
+    }
+}
+
+/// Error returned by the [Mediator].
+#[derive(Debug)]
+pub enum MediatorError {
+    /// No handler is registered for the request type.
+    HandlerNotFound {
+        /// Name of the request type.
+        request: &'static str,
+    },
+    /// A handler is already registered for the request type.
+    AlreadyRegistered {
+        /// Name of the request type.
+        request: &'static str,
+    },
+    /// The request handler failed.
+    Handler(BoxError),
+}
+
+impl std::fmt::Display for MediatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HandlerNotFound { request } => write!(f, "No handler registered for {}", request),
+            Self::AlreadyRegistered { request } => {
+                write!(f, "Handler already registered for {}", request)
+            }
+            Self::Handler(e) => write!(f, "Request handler error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MediatorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::HandlerNotFound { .. } | Self::AlreadyRegistered { .. } => None,
+            Self::Handler(e) => Some(e.as_ref()),
+        }
+    }
+}
@@ -1,5 +1,29 @@
+mod clock;
+pub use clock::*;
+
+mod event_sourced_repository;
+pub use event_sourced_repository::*;
+
+mod event_store;
+pub use event_store::*;
+
+mod mediator;
+pub use mediator::*;
+
 mod repository;
 pub use repository::*;
 
+mod saga;
+pub use saga::*;
+
 mod service;
 pub use service::*;
+
+mod snapshot;
+pub use snapshot::*;
+
+mod soft_delete;
+pub use soft_delete::*;
+
+mod tenant;
+pub use tenant::*;
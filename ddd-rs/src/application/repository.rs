@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
-use crate::domain::{AggregateRoot, AggregateRootEx, Entity};
+use crate::domain::{
+    AggregateRoot, AggregateRootEx, DomainEventEnvelope, Entity, IdGenerator, InvariantError,
+    Specification, TimeOrderedIdGenerator,
+};
+use crate::BoxError;
 
-use super::DomainEventHandler;
+use super::{Clock, DomainEventHandler, SystemClock};
 
 /// Trait for representing a **Repository**.
 ///
@@ -74,16 +78,30 @@ use super::DomainEventHandler;
 #[async_trait::async_trait]
 pub trait Repository<T: AggregateRoot>: ReadRepository<T> {
     /// Adds an entity to the repository.
-    async fn add(&self, entity: T) -> crate::Result<T>;
+    ///
+    /// This should fail with [RepositoryError::AlreadyExists] if an entity with the same ID exists
+    /// and with [RepositoryError::InvariantViolation] if the entity's
+    /// [invariants](AggregateRoot::as_invariants) do not hold.
+    async fn add(&self, entity: T) -> crate::Result<T, RepositoryError>;
 
     /// Updates an entity on the repository.
-    async fn update(&self, entity: T) -> crate::Result<T>;
+    ///
+    /// This should fail with [RepositoryError::NotFound] if the entity does not exist, with
+    /// [RepositoryError::InvariantViolation] if the entity's
+    /// [invariants](AggregateRoot::as_invariants) do not hold and, for
+    /// [Versioned](crate::domain::Versioned) aggregates, with [RepositoryError::Concurrency] if the
+    /// stored version differs from the entity's.
+    async fn update(&self, entity: T) -> crate::Result<T, RepositoryError>;
 
     /// Deletes the entity from the repository.
-    async fn delete(&self, entity: T) -> crate::Result<()>;
+    ///
+    /// This should fail with [RepositoryError::NotFound] if the entity does not exist and, for
+    /// [Versioned](crate::domain::Versioned) aggregates, with [RepositoryError::Concurrency] if the
+    /// stored version differs from the entity's.
+    async fn delete(&self, entity: T) -> crate::Result<(), RepositoryError>;
 
     /// Adds the given entities to the repository.
-    async fn add_range(&self, entities: Vec<T>) -> crate::Result<Vec<T>> {
+    async fn add_range(&self, entities: Vec<T>) -> crate::Result<Vec<T>, RepositoryError> {
         let mut added_entities = Vec::new();
 
         for entity in entities {
@@ -94,7 +112,7 @@ pub trait Repository<T: AggregateRoot>: ReadRepository<T> {
     }
 
     /// Updates the given entities on the repository.
-    async fn update_range(&self, entities: Vec<T>) -> crate::Result<Vec<T>> {
+    async fn update_range(&self, entities: Vec<T>) -> crate::Result<Vec<T>, RepositoryError> {
         let mut updated_entities = Vec::new();
 
         for entity in entities {
@@ -107,7 +125,7 @@ pub trait Repository<T: AggregateRoot>: ReadRepository<T> {
     }
 
     /// Deletes the given entities from the repository.
-    async fn delete_range(&self, entities: Vec<T>) -> crate::Result<()> {
+    async fn delete_range(&self, entities: Vec<T>) -> crate::Result<(), RepositoryError> {
         for entity in entities {
             self.delete(entity).await?;
         }
@@ -116,34 +134,239 @@ pub trait Repository<T: AggregateRoot>: ReadRepository<T> {
     }
 }
 
+/// Error returned when persisting a [Versioned](crate::domain::Versioned) aggregate whose version
+/// differs from the one stored in the repository, meaning it was concurrently modified.
+///
+/// See [Versioned](crate::domain::Versioned) for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcurrencyError {
+    /// Version of the aggregate being persisted.
+    pub expected: u64,
+    /// Version of the stored aggregate.
+    pub actual: u64,
+}
+
+impl std::fmt::Display for ConcurrencyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected aggregate version {}, but found version {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ConcurrencyError {}
+
+/// Error returned by [Repository] and [ReadRepository] operations.
+///
+/// Errors of other kinds (e.g. I/O errors) are wrapped as [Storage](RepositoryError::Storage)
+/// errors, including when converting from a [BoxError], unless it holds one of the other kinds.
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::{
+///     application::{ConcurrencyError, ReadRepository, Repository, RepositoryError},
+///     infrastructure::InMemoryRepository,
+///     BoxError,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct MyEntity {
+///     #[entity(id)]
+///     id: u32,
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let repository = InMemoryRepository::new();
+///
+/// repository.add(MyEntity { id: 1 }).await.unwrap();
+///
+/// assert!(matches!(
+///     repository.add(MyEntity { id: 1 }).await,
+///     Err(RepositoryError::AlreadyExists)
+/// ));
+///
+/// assert!(matches!(
+///     repository.update(MyEntity { id: 2 }).await,
+///     Err(RepositoryError::NotFound)
+/// ));
+///
+/// // Repository errors interoperate with type-erased errors, in both directions.
+/// let error: BoxError = RepositoryError::NotFound.into();
+///
+/// assert!(matches!(RepositoryError::from(error), RepositoryError::NotFound));
+///
+/// let error: BoxError = ConcurrencyError { expected: 1, actual: 2 }.into();
+///
+/// assert!(matches!(
+///     RepositoryError::from(error),
+///     RepositoryError::Concurrency(ConcurrencyError { expected: 1, actual: 2 })
+/// ));
+///
+/// let error: BoxError = "Connection refused".into();
+///
+/// assert!(matches!(RepositoryError::from(error), RepositoryError::Storage(_)));
+/// # })
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RepositoryError {
+    /// The entity does not exist in the repository.
+    NotFound,
+    /// An entity with the same ID already exists in the repository.
+    AlreadyExists,
+    /// The entity was concurrently modified.
+    Concurrency(ConcurrencyError),
+    /// The entity's invariants do not hold.
+    InvariantViolation(InvariantError),
+    /// The entity belongs to another tenant than the repository's.
+    CrossTenant,
+    /// The repository does not support the operation, which should not be retried.
+    Unsupported(&'static str),
+    /// The underlying storage failed.
+    Storage(BoxError),
+}
+
+impl std::fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => f.write_str("Entity not found"),
+            Self::AlreadyExists => f.write_str("Entity already exists"),
+            Self::Concurrency(e) => e.fmt(f),
+            Self::InvariantViolation(e) => e.fmt(f),
+            Self::CrossTenant => f.write_str("Entity belongs to another tenant"),
+            Self::Unsupported(operation) => write!(f, "Unsupported operation: {}", operation),
+            Self::Storage(e) => write!(f, "Storage error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotFound | Self::AlreadyExists | Self::CrossTenant | Self::Unsupported(_) => None,
+            Self::Concurrency(e) => Some(e),
+            Self::InvariantViolation(e) => Some(e),
+            Self::Storage(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl From<ConcurrencyError> for RepositoryError {
+    fn from(e: ConcurrencyError) -> Self {
+        Self::Concurrency(e)
+    }
+}
+
+impl From<InvariantError> for RepositoryError {
+    fn from(e: InvariantError) -> Self {
+        Self::InvariantViolation(e)
+    }
+}
+
+impl From<BoxError> for RepositoryError {
+    fn from(e: BoxError) -> Self {
+        let e = match e.downcast::<RepositoryError>() {
+            Ok(e) => return *e,
+            Err(e) => e,
+        };
+
+        let e = match e.downcast::<ConcurrencyError>() {
+            Ok(e) => return Self::Concurrency(*e),
+            Err(e) => e,
+        };
+
+        match e.downcast::<InvariantError>() {
+            Ok(e) => Self::InvariantViolation(*e),
+            Err(e) => Self::Storage(e),
+        }
+    }
+}
+
 /// Trait for representing a read-only **Repository**.
 ///
 /// See the [Repository] trait for the definition of a repository and a sample of its usage.
 #[async_trait::async_trait]
 pub trait ReadRepository<T: AggregateRoot>: Send + Sync {
     /// Gets an entity with the given ID.
-    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>>;
+    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>, RepositoryError>;
 
     /// Lists all entities within a given page.
-    async fn list(&self, skip: usize, take: usize) -> crate::Result<Vec<T>>;
+    async fn list(&self, skip: usize, take: usize) -> crate::Result<Vec<T>, RepositoryError>;
 
     /// Returns the total number of entities in the repository.
-    async fn count(&self) -> crate::Result<usize>;
+    async fn count(&self) -> crate::Result<usize, RepositoryError>;
 
     /// Checks whether an entity with the given ID exists in the repository.
-    async fn exists(&self, id: <T as Entity>::Id) -> crate::Result<bool> {
+    async fn exists(&self, id: <T as Entity>::Id) -> crate::Result<bool, RepositoryError> {
         self.get_by_id(id).await.map(|e| e.is_some())
     }
 
     /// Checks if the repository is empty.
-    async fn is_empty(&self) -> crate::Result<bool> {
+    async fn is_empty(&self) -> crate::Result<bool, RepositoryError> {
         self.count().await.map(|c| c == 0)
     }
+
+    /// Finds all entities that satisfy the given [Specification].
+    ///
+    /// The default implementation lists and filters every entity in the repository, so
+    /// implementations should override it with a proper query whenever possible.
+    async fn find(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Vec<T>, RepositoryError> {
+        let count = self.count().await?;
+
+        let entities = self.list(0, count).await?;
+
+        Ok(entities
+            .into_iter()
+            .filter(|e| specification.is_satisfied_by(e))
+            .collect())
+    }
+
+    /// Finds an entity that satisfies the given [Specification].
+    async fn find_one(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Option<T>, RepositoryError> {
+        self.find(specification).await.map(|e| e.into_iter().next())
+    }
+
+    /// Returns the number of entities that satisfy the given [Specification].
+    async fn count_by(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<usize, RepositoryError> {
+        self.find(specification).await.map(|e| e.len())
+    }
+
+    /// Checks whether any entity satisfies the given [Specification].
+    async fn exists_by(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<bool, RepositoryError> {
+        self.count_by(specification).await.map(|c| c > 0)
+    }
 }
 
 /// Repository extension abstraction, for performing operations over aggregates that implement the
 /// [AggregateRootEx] trait.
 ///
+/// Aggregates have their [Invariants](crate::domain::Invariants) checked by the underlying
+/// repository, hence their domain events are only handled once they are persisted. They are only
+/// taken from the aggregate at that point, so that the underlying repository counts them as
+/// [pending changes](AggregateRoot::pending_changes), but it must not persist them.
+///
+/// Each domain event is numbered within the aggregate's history, starting from the version of the
+/// aggregate being persisted, and [Versioned](crate::domain::Versioned) aggregates have their
+/// version right after each event recorded along with it.
+/// [ChangeTracked](crate::domain::ChangeTracked) aggregates have their changes cleared once
+/// persisted by the underlying repository, which may query them in order to persist only the
+/// modified fields.
+///
 /// # Examples
 ///
 /// Building upon the [Repository] sample, this example shows how a repository object can be
@@ -154,6 +377,7 @@ pub trait ReadRepository<T: AggregateRoot>: Send + Sync {
 ///
 /// use ddd_rs::{
 ///     application::{DomainEventHandler, ReadRepository, Repository, RepositoryEx},
+///     domain::DomainEventEnvelope,
 ///     infrastructure::InMemoryRepository
 /// };
 ///
@@ -218,8 +442,12 @@ pub trait ReadRepository<T: AggregateRoot>: Send + Sync {
 ///
 /// #[async_trait::async_trait]
 /// impl DomainEventHandler<MyEntity> for MyDomainEventHandler {
-///     async fn handle(&self, mut entity: MyEntity, event: MyDomainEvent) -> ddd_rs::Result<MyEntity> {
-///         let action = match event {
+///     async fn handle(
+///         &self,
+///         mut entity: MyEntity,
+///         envelope: DomainEventEnvelope<MyDomainEvent, u32>,
+///     ) -> ddd_rs::Result<MyEntity> {
+///         let action = match envelope.event {
 ///             MyDomainEvent::AsyncActionRequested { action, .. } => action,
 ///         };
 ///
@@ -227,7 +455,7 @@ pub trait ReadRepository<T: AggregateRoot>: Send + Sync {
 ///
 ///         entity.confirm_async_action_performed(action);
 ///
-///         self.repository.update(entity).await
+///         Ok(self.repository.update(entity).await?)
 ///     }
 /// }
 ///
@@ -258,13 +486,115 @@ pub trait ReadRepository<T: AggregateRoot>: Send + Sync {
 /// assert!(entity.domain_events.is_empty());
 /// # })
 /// ```
+///
+/// Each domain event is handled within a [DomainEventEnvelope], carrying its metadata. Inject a
+/// [Clock] and an [IdGenerator] to keep them deterministic:
+///
+/// ```
+/// use std::{
+///     sync::{Arc, Mutex},
+///     time::{Duration, SystemTime},
+/// };
+///
+/// use ddd_rs::{
+///     application::{DomainEventHandler, FixedClock, ReadRepository, Repository, RepositoryEx},
+///     domain::{AggregateRootEx, DomainEventEnvelope, SequentialIdGenerator},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum OrderEvent {
+///     Placed,
+///     Paid,
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Order {
+///     #[entity(id)]
+///     id: u32,
+///     #[aggregate_root(version)]
+///     version: u64,
+///     #[aggregate_root(domain_events)]
+///     domain_events: Vec<OrderEvent>,
+/// }
+///
+/// #[derive(Default)]
+/// struct EventLog {
+///     envelopes: Mutex<Vec<DomainEventEnvelope<OrderEvent, u32>>>,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl DomainEventHandler<Order> for EventLog {
+///     async fn handle(
+///         &self,
+///         entity: Order,
+///         envelope: DomainEventEnvelope<OrderEvent, u32>,
+///     ) -> ddd_rs::Result<Order> {
+///         self.envelopes.lock().unwrap().push(envelope);
+///
+///         Ok(entity)
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let clock = Arc::new(FixedClock::new(SystemTime::UNIX_EPOCH));
+/// let event_log = Arc::new(EventLog::default());
+///
+/// let repository = RepositoryEx::new(event_log.clone(), Arc::new(InMemoryRepository::new()))
+///     .with_clock(clock.clone())
+///     .with_event_id_generator(Arc::new(SequentialIdGenerator::starting_at(100)));
+///
+/// let mut order = Order { id: 1, version: 0, domain_events: vec![] };
+///
+/// order.register_domain_event(OrderEvent::Placed);
+/// order.register_domain_event(OrderEvent::Paid);
+///
+/// clock.advance(Duration::from_secs(60));
+///
+/// repository.add(order).await.unwrap();
+///
+/// let envelopes = event_log.envelopes.lock().unwrap().clone();
+/// let occurred_at = SystemTime::UNIX_EPOCH + Duration::from_secs(60);
+///
+/// assert_eq!(
+///     envelopes,
+///     [
+///         DomainEventEnvelope::new(100, occurred_at, 1, OrderEvent::Placed)
+///             .with_aggregate_version(1)
+///             .with_sequence(1),
+///         DomainEventEnvelope::new(101, occurred_at, 1, OrderEvent::Paid)
+///             .with_aggregate_version(2)
+///             .with_sequence(2),
+///     ]
+/// );
+///
+/// // Sequences follow the aggregate's history, which advances by one version per event.
+/// let mut order = repository.get_by_id(1).await.unwrap().unwrap();
+///
+/// assert_eq!(order.version, 2);
+///
+/// order.register_domain_event(OrderEvent::Paid);
+///
+/// let order = repository.update(order).await.unwrap();
+///
+/// let envelope = event_log.envelopes.lock().unwrap().pop().unwrap();
+///
+/// assert_eq!(order.version, 3);
+/// assert_eq!((envelope.sequence, envelope.aggregate_version), (3, Some(3)));
+/// # })
+/// ```
 pub struct RepositoryEx<T: AggregateRootEx> {
     domain_event_handler: Arc<dyn DomainEventHandler<T>>,
     repository: Arc<dyn Repository<T>>,
+    clock: Arc<dyn Clock>,
+    event_id_generator: Arc<dyn IdGenerator<Id = u128>>,
 }
 
 impl<T: AggregateRootEx> RepositoryEx<T> {
     /// Creates a new instance of the extended repository.
+    ///
+    /// Domain events are timestamped by the [SystemClock], and identified by the
+    /// [TimeOrderedIdGenerator], which is globally unique.
     pub fn new(
         domain_event_handler: Arc<dyn DomainEventHandler<T>>,
         repository: Arc<dyn Repository<T>>,
@@ -272,60 +602,251 @@ impl<T: AggregateRootEx> RepositoryEx<T> {
         Self {
             domain_event_handler,
             repository,
+            clock: Arc::new(SystemClock),
+            event_id_generator: Arc::new(TimeOrderedIdGenerator),
+        }
+    }
+
+    /// Sets the [Clock] used for timestamping domain events.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets the [IdGenerator] used for identifying domain events.
+    ///
+    /// Event IDs should be unique across every aggregate and process, hence stateful generators
+    /// (e.g. a [SequentialIdGenerator](crate::domain::SequentialIdGenerator)) are mostly useful for
+    /// tests.
+    pub fn with_event_id_generator<G>(mut self, event_id_generator: Arc<G>) -> Self
+    where
+        G: IdGenerator + ?Sized + 'static,
+        G::Id: Into<u128>,
+    {
+        self.event_id_generator = Arc::new(EventIdGenerator(event_id_generator));
+        self
+    }
+
+    /// Adds an entity to the repository, as a consequence of handling the given domain event.
+    ///
+    /// The domain events of the entity are correlated with the given one.
+    pub async fn add_caused_by<E: Sync, Id: Sync>(
+        &self,
+        entity: T,
+        cause: &DomainEventEnvelope<E, Id>,
+    ) -> crate::Result<T, RepositoryError> {
+        self.add_with_cause(entity, Some(Cause::of(cause))).await
+    }
+
+    /// Updates an entity on the repository, as a consequence of handling the given domain event.
+    ///
+    /// The domain events of the entity are correlated with the given one.
+    pub async fn update_caused_by<E: Sync, Id: Sync>(
+        &self,
+        entity: T,
+        cause: &DomainEventEnvelope<E, Id>,
+    ) -> crate::Result<T, RepositoryError> {
+        self.update_with_cause(entity, Some(Cause::of(cause))).await
+    }
+
+    /// Deletes the entity from the repository, as a consequence of handling the given domain event.
+    ///
+    /// The domain events of the entity are correlated with the given one.
+    pub async fn delete_caused_by<E: Sync, Id: Sync>(
+        &self,
+        entity: T,
+        cause: &DomainEventEnvelope<E, Id>,
+    ) -> crate::Result<(), RepositoryError> {
+        self.delete_with_cause(entity, Some(Cause::of(cause))).await
+    }
+
+    async fn add_with_cause(
+        &self,
+        entity: T,
+        cause: Option<Cause>,
+    ) -> crate::Result<T, RepositoryError> {
+        let previous_version = version_of(&entity);
+
+        // Domain events are only taken once persisted, so that the underlying repository counts
+        // them as changes of the aggregate.
+        let mut entity = clear_changes(self.repository.add(entity).await?);
+        let domain_events = entity.take_domain_events();
+
+        Ok(self
+            .handle_domain_events(entity, previous_version, domain_events, cause)
+            .await?)
+    }
+
+    async fn update_with_cause(
+        &self,
+        entity: T,
+        cause: Option<Cause>,
+    ) -> crate::Result<T, RepositoryError> {
+        let previous_version = version_of(&entity);
+
+        let mut entity = clear_changes(self.repository.update(entity).await?);
+        let domain_events = entity.take_domain_events();
+
+        Ok(self
+            .handle_domain_events(entity, previous_version, domain_events, cause)
+            .await?)
+    }
+
+    async fn delete_with_cause(
+        &self,
+        mut entity: T,
+        cause: Option<Cause>,
+    ) -> crate::Result<(), RepositoryError> {
+        let previous_version = version_of(&entity);
+        let domain_events = entity.take_domain_events();
+
+        let entity = self
+            .handle_domain_events(entity, previous_version, domain_events, cause)
+            .await?;
+
+        self.repository.delete(entity).await
+    }
+
+    async fn handle_domain_events(
+        &self,
+        mut entity: T,
+        previous_version: Option<u64>,
+        domain_events: Vec<T::DomainEvent>,
+        cause: Option<Cause>,
+    ) -> crate::Result<T> {
+        let aggregate_id = entity.id().clone();
+
+        let envelopes = domain_events
+            .into_iter()
+            .zip(1..)
+            .map(|(event, position)| {
+                let sequence = previous_version.unwrap_or(0) + position;
+
+                let mut envelope = DomainEventEnvelope::new(
+                    self.event_id_generator.next_id(),
+                    self.clock.now(),
+                    aggregate_id.clone(),
+                    event,
+                )
+                .with_sequence(sequence);
+
+                envelope.aggregate_version = previous_version.map(|_| sequence);
+
+                if let Some(cause) = &cause {
+                    envelope.correlation_id = Some(cause.correlation_id);
+                    envelope.causation_id = Some(cause.causation_id);
+                }
+
+                envelope
+            })
+            .collect::<Vec<_>>();
+
+        for envelope in envelopes {
+            entity = self.domain_event_handler.handle(entity, envelope).await?;
         }
+
+        Ok(entity)
     }
 }
 
 #[async_trait::async_trait]
 impl<T: AggregateRootEx> ReadRepository<T> for RepositoryEx<T> {
-    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>> {
+    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>, RepositoryError> {
         self.repository.get_by_id(id).await
     }
 
-    async fn list(&self, skip: usize, take: usize) -> crate::Result<Vec<T>> {
+    async fn list(&self, skip: usize, take: usize) -> crate::Result<Vec<T>, RepositoryError> {
         self.repository.list(skip, take).await
     }
 
-    async fn count(&self) -> crate::Result<usize> {
+    async fn count(&self) -> crate::Result<usize, RepositoryError> {
         self.repository.count().await
     }
+
+    async fn find(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Vec<T>, RepositoryError> {
+        self.repository.find(specification).await
+    }
+
+    async fn find_one(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Option<T>, RepositoryError> {
+        self.repository.find_one(specification).await
+    }
+
+    async fn count_by(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<usize, RepositoryError> {
+        self.repository.count_by(specification).await
+    }
+
+    async fn exists_by(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<bool, RepositoryError> {
+        self.repository.exists_by(specification).await
+    }
 }
 
 #[async_trait::async_trait]
 impl<T: AggregateRootEx> Repository<T> for RepositoryEx<T> {
-    async fn add(&self, mut entity: T) -> crate::Result<T> {
-        let domain_events = entity.take_domain_events();
-
-        let mut entity = self.repository.add(entity).await?;
-
-        for event in domain_events {
-            entity = self.domain_event_handler.handle(entity, event).await?;
-        }
+    async fn add(&self, entity: T) -> crate::Result<T, RepositoryError> {
+        self.add_with_cause(entity, None).await
+    }
 
-        Ok(entity)
+    async fn update(&self, entity: T) -> crate::Result<T, RepositoryError> {
+        self.update_with_cause(entity, None).await
     }
 
-    async fn update(&self, mut entity: T) -> crate::Result<T> {
-        let domain_events = entity.take_domain_events();
+    async fn delete(&self, entity: T) -> crate::Result<(), RepositoryError> {
+        self.delete_with_cause(entity, None).await
+    }
+}
 
-        let mut entity = self.repository.update(entity).await?;
+/// [IdGenerator] of event IDs, converted from those of the inner generator.
+struct EventIdGenerator<G: ?Sized>(Arc<G>);
 
-        for event in domain_events {
-            entity = self.domain_event_handler.handle(entity, event).await?;
-        }
+impl<G> IdGenerator for EventIdGenerator<G>
+where
+    G: IdGenerator + ?Sized,
+    G::Id: Into<u128>,
+{
+    type Id = u128;
 
-        Ok(entity)
+    fn next_id(&self) -> Self::Id {
+        self.0.next_id().into()
     }
+}
 
-    async fn delete(&self, mut entity: T) -> crate::Result<()> {
-        let domain_events = entity.take_domain_events();
-
-        let mut entity = entity;
+/// Correlation of the domain events being handled with the one that caused them.
+struct Cause {
+    correlation_id: u128,
+    causation_id: u128,
+}
 
-        for event in domain_events {
-            entity = self.domain_event_handler.handle(entity, event).await?;
+impl Cause {
+    fn of<E, Id>(envelope: &DomainEventEnvelope<E, Id>) -> Self {
+        Self {
+            correlation_id: envelope.correlation_id.unwrap_or(envelope.event_id),
+            causation_id: envelope.event_id,
         }
+    }
+}
 
-        self.repository.delete(entity).await
+fn version_of<T: AggregateRoot>(entity: &T) -> Option<u64> {
+    entity.as_versioned().map(|v| v.version())
+}
+
+fn clear_changes<T: AggregateRoot>(mut entity: T) -> T {
+    if let Some(change_tracked) = entity.as_change_tracked_mut() {
+        change_tracked.clear_changes();
     }
+
+    entity.clear_collection_changes();
+
+    entity
 }
@@ -0,0 +1,532 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::BoxError;
+
+use super::{Clock, SystemClock};
+
+/// Status of a [Saga].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SagaStatus {
+    /// The saga is still handling events.
+    Running,
+    /// The saga finished successfully.
+    Completed,
+    /// The saga was aborted, and its compensating actions are pending.
+    Compensating,
+    /// The saga was aborted, and its compensating actions were performed.
+    Compensated,
+}
+
+/// A **Saga**, i.e. the persisted state of a single instance of a [ProcessManager], identified by
+/// the key its events are correlated with.
+///
+/// See [ProcessManager] for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Saga<K, S> {
+    /// Key the saga's events are correlated with.
+    pub key: K,
+    /// State of the process, owned by the [ProcessManager].
+    pub state: S,
+    /// Status of the saga.
+    pub status: SagaStatus,
+    /// Instant after which the saga times out, if it is still running.
+    pub deadline: Option<SystemTime>,
+    /// Version of the saga, used by [SagaStores](SagaStore) for optimistic concurrency control.
+    pub version: u64,
+}
+
+impl<K, S> Saga<K, S> {
+    /// Creates a new, running [Saga].
+    pub fn new(key: K, state: S) -> Self {
+        Self {
+            key,
+            state,
+            status: SagaStatus::Running,
+            deadline: None,
+            version: 0,
+        }
+    }
+
+    /// Checks whether the saga is still handling events.
+    pub fn is_running(&self) -> bool {
+        self.status == SagaStatus::Running
+    }
+
+    /// Checks whether the saga is running past its deadline.
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.is_running() && self.deadline.is_some_and(|deadline| deadline <= now)
+    }
+
+    /// Sets the instant after which the saga times out.
+    pub fn set_deadline(&mut self, deadline: SystemTime) {
+        self.deadline = Some(deadline);
+    }
+
+    /// Clears the saga's deadline, so that it no longer times out.
+    pub fn clear_deadline(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Marks the saga as successfully completed.
+    pub fn complete(&mut self) {
+        self.status = SagaStatus::Completed;
+    }
+
+    /// Aborts the saga, so that its compensating actions are performed.
+    pub fn abort(&mut self) {
+        self.status = SagaStatus::Compensating;
+    }
+}
+
+/// Trait for representing a **Process Manager**, which coordinates a workflow across multiple
+/// aggregates.
+///
+/// > A process manager maintains the state of the sequence and determines the next processing step
+/// > based on intermediate results.
+///
+/// Incoming events are correlated with a key, which identifies the [Saga] (i.e. the instance of
+/// the workflow) that handles them. The process manager reacts to them by updating the saga's
+/// state and issuing commands, usually through [RequestHandlers](super::RequestHandler).
+///
+/// Sagas are aborted when handling an event fails, when the process manager
+/// [aborts](Saga::abort) them or, by default, when they time out. Aborted sagas have their
+/// [compensating actions](ProcessManager::compensate) performed, in order to undo the steps that
+/// were already taken.
+///
+/// Commands are issued while handling an event, before the saga is saved. Should saving it fail
+/// (e.g. with a [ConcurrencyError](super::ConcurrencyError), due to the same saga handling another
+/// event concurrently), the commands were issued nonetheless, and will be issued again if the event
+/// is redelivered. Hence, the handlers of these commands must be **idempotent**.
+///
+/// # Examples
+///
+/// ```
+/// use std::{
+///     convert::Infallible,
+///     sync::{Arc, Mutex},
+///     time::{Duration, SystemTime},
+/// };
+///
+/// use ddd_rs::{
+///     application::{
+///         Clock, Command, CommandHandler, FixedClock, ProcessManager, Saga, SagaRunner,
+///         SagaStatus, SagaStore,
+///     },
+///     infrastructure::InMemorySagaStore,
+/// };
+///
+/// // Events from the order, payment and shipment aggregates.
+/// enum OrderEvent {
+///     Placed { order_id: u32, amount: u64 },
+///     PaymentCaptured { order_id: u32 },
+///     Shipped { order_id: u32 },
+///     ShipmentFailed { order_id: u32 },
+/// }
+///
+/// // Commands issued to the payment and shipment services.
+/// struct ChargePayment {
+///     order_id: u32,
+///     amount: u64,
+/// }
+///
+/// impl Command for ChargePayment {}
+///
+/// struct RefundPayment {
+///     order_id: u32,
+/// }
+///
+/// impl Command for RefundPayment {}
+///
+/// struct ShipOrder {
+///     order_id: u32,
+/// }
+///
+/// impl Command for ShipOrder {}
+///
+/// #[derive(Default)]
+/// struct Services {
+///     log: Mutex<Vec<String>>,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl CommandHandler<ChargePayment> for Services {
+///     type Error = Infallible;
+///
+///     async fn handle(&self, command: ChargePayment) -> Result<(), Infallible> {
+///         let entry = format!("charge #{}: ${}", command.order_id, command.amount);
+///
+///         self.log.lock().unwrap().push(entry);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl CommandHandler<RefundPayment> for Services {
+///     type Error = Infallible;
+///
+///     async fn handle(&self, command: RefundPayment) -> Result<(), Infallible> {
+///         self.log.lock().unwrap().push(format!("refund #{}", command.order_id));
+///
+///         Ok(())
+///     }
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl CommandHandler<ShipOrder> for Services {
+///     type Error = Infallible;
+///
+///     async fn handle(&self, command: ShipOrder) -> Result<(), Infallible> {
+///         self.log.lock().unwrap().push(format!("ship #{}", command.order_id));
+///
+///         Ok(())
+///     }
+/// }
+///
+/// #[derive(Clone)]
+/// struct Fulfillment {
+///     amount: u64,
+///     paid: bool,
+/// }
+///
+/// // The process manager coordinates an order's fulfillment, from payment to shipment.
+/// struct OrderFulfillment {
+///     services: Arc<Services>,
+///     clock: Arc<FixedClock>,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl ProcessManager for OrderFulfillment {
+///     type Key = u32;
+///     type Event = OrderEvent;
+///     type State = Fulfillment;
+///
+///     fn correlate(&self, event: &OrderEvent) -> Option<u32> {
+///         match event {
+///             OrderEvent::Placed { order_id, .. }
+///             | OrderEvent::PaymentCaptured { order_id }
+///             | OrderEvent::Shipped { order_id }
+///             | OrderEvent::ShipmentFailed { order_id } => Some(*order_id),
+///         }
+///     }
+///
+///     fn start(&self, event: &OrderEvent) -> Option<Fulfillment> {
+///         match event {
+///             OrderEvent::Placed { amount, .. } => Some(Fulfillment { amount: *amount, paid: false }),
+///             _ => None,
+///         }
+///     }
+///
+///     async fn handle(
+///         &self,
+///         saga: &mut Saga<u32, Fulfillment>,
+///         event: OrderEvent,
+///     ) -> ddd_rs::Result<()> {
+///         let order_id = saga.key;
+///
+///         match event {
+///             OrderEvent::Placed { .. } => {
+///                 let amount = saga.state.amount;
+///
+///                 self.services.handle(ChargePayment { order_id, amount }).await?;
+///
+///                 // Payments must be captured within 30 minutes.
+///                 saga.set_deadline(self.clock.now() + Duration::from_secs(30 * 60));
+///             }
+///             OrderEvent::PaymentCaptured { .. } => {
+///                 saga.state.paid = true;
+///                 saga.clear_deadline();
+///
+///                 self.services.handle(ShipOrder { order_id }).await?;
+///             }
+///             OrderEvent::Shipped { .. } => saga.complete(),
+///             OrderEvent::ShipmentFailed { .. } => saga.abort(),
+///         }
+///
+///         Ok(())
+///     }
+///
+///     async fn compensate(&self, saga: &mut Saga<u32, Fulfillment>) -> ddd_rs::Result<()> {
+///         if saga.state.paid {
+///             self.services.handle(RefundPayment { order_id: saga.key }).await?;
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let clock = Arc::new(FixedClock::new(SystemTime::UNIX_EPOCH));
+/// let services = Arc::new(Services::default());
+/// let saga_store = Arc::new(InMemorySagaStore::new());
+///
+/// let process_manager = OrderFulfillment {
+///     services: services.clone(),
+///     clock: clock.clone(),
+/// };
+///
+/// let runner = SagaRunner::new(Arc::new(process_manager), saga_store.clone())
+///     .with_clock(clock.clone());
+///
+/// // Events that neither start nor belong to a saga are ignored.
+/// assert!(runner.handle(OrderEvent::Shipped { order_id: 9 }).await.unwrap().is_none());
+///
+/// // The first order is paid and shipped.
+/// runner.handle(OrderEvent::Placed { order_id: 1, amount: 100 }).await.unwrap();
+/// runner.handle(OrderEvent::PaymentCaptured { order_id: 1 }).await.unwrap();
+///
+/// let saga = runner.handle(OrderEvent::Shipped { order_id: 1 }).await.unwrap().unwrap();
+///
+/// assert_eq!(saga.status, SagaStatus::Completed);
+///
+/// // The second order fails to be shipped, hence its payment is refunded.
+/// runner.handle(OrderEvent::Placed { order_id: 2, amount: 50 }).await.unwrap();
+/// runner.handle(OrderEvent::PaymentCaptured { order_id: 2 }).await.unwrap();
+///
+/// let saga = runner.handle(OrderEvent::ShipmentFailed { order_id: 2 }).await.unwrap().unwrap();
+///
+/// assert_eq!(saga.status, SagaStatus::Compensated);
+///
+/// // The third order is never paid, hence it times out.
+/// runner.handle(OrderEvent::Placed { order_id: 3, amount: 20 }).await.unwrap();
+///
+/// clock.advance(Duration::from_secs(60 * 60));
+///
+/// let timed_out = runner.check_timeouts().await.unwrap();
+///
+/// assert_eq!(timed_out.len(), 1);
+/// assert_eq!(timed_out[0].as_ref().unwrap().key, 3);
+///
+/// let saga = saga_store.load(&3).await.unwrap().unwrap();
+///
+/// assert_eq!(saga.status, SagaStatus::Compensated);
+/// assert_eq!(saga.deadline, None);
+///
+/// assert_eq!(
+///     *services.log.lock().unwrap(),
+///     [
+///         "charge #1: $100",
+///         "ship #1",
+///         "charge #2: $50",
+///         "ship #2",
+///         "refund #2",
+///         "charge #3: $20",
+///     ]
+/// );
+/// # })
+/// ```
+#[async_trait::async_trait]
+pub trait ProcessManager: Send + Sync {
+    /// Key the events are correlated with, identifying the [Saga] that handles them.
+    type Key: Clone + Send + Sync;
+
+    /// Incoming event type.
+    type Event: Send;
+
+    /// Process state type, persisted along with the [Saga].
+    type State: Send + Sync;
+
+    /// Correlates the event with the key of the [Saga] that handles it, if any.
+    fn correlate(&self, event: &Self::Event) -> Option<Self::Key>;
+
+    /// Returns the initial state of a new [Saga], if the event starts one.
+    fn start(&self, event: &Self::Event) -> Option<Self::State>;
+
+    /// Handles an event correlated with the running [Saga].
+    ///
+    /// Commands issued here may be issued again, should saving the saga fail afterwards, hence
+    /// their handlers must be idempotent.
+    async fn handle(
+        &self,
+        saga: &mut Saga<Self::Key, Self::State>,
+        event: Self::Event,
+    ) -> crate::Result<()>;
+
+    /// Handles the running [Saga] reaching its deadline.
+    ///
+    /// By default, the saga is aborted. Otherwise, the saga should either be completed or have its
+    /// deadline extended, lest it time out again.
+    async fn on_timeout(&self, saga: &mut Saga<Self::Key, Self::State>) -> crate::Result<()> {
+        saga.abort();
+
+        Ok(())
+    }
+
+    /// Performs the compensating actions of the aborted [Saga], undoing the steps that were
+    /// already taken.
+    async fn compensate(&self, saga: &mut Saga<Self::Key, Self::State>) -> crate::Result<()>;
+}
+
+/// Trait for representing a **Saga Store**, which persists the [Sagas](Saga) of a
+/// [ProcessManager].
+///
+/// Saga stores should check the version of the saga being saved against the one of the stored
+/// saga, rejecting the write with a [ConcurrencyError](super::ConcurrencyError) if they differ, and
+/// then increment it.
+///
+/// See [ProcessManager] for an example.
+#[async_trait::async_trait]
+pub trait SagaStore<K: Send + Sync, S: Send>: Send + Sync {
+    /// Saves the saga, returning it with its incremented version.
+    async fn save(&self, saga: Saga<K, S>) -> crate::Result<Saga<K, S>>;
+
+    /// Loads the saga with the given key.
+    async fn load(&self, key: &K) -> crate::Result<Option<Saga<K, S>>>;
+
+    /// Finds the running sagas whose deadline is not after the given instant.
+    async fn find_expired(&self, now: SystemTime) -> crate::Result<Vec<Saga<K, S>>>;
+}
+
+/// Runs a [ProcessManager], dispatching incoming events to its [Sagas](Saga) and persisting them
+/// in a [SagaStore].
+///
+/// Aborted sagas have their compensating actions performed before being saved. Should those fail,
+/// the saga is saved as [Compensating](SagaStatus::Compensating), so that it can be inspected.
+///
+/// Errors are returned as they occurred, unless recovering from them (i.e. compensating or saving
+/// the aborted saga) fails as well, in which case both are returned as a [SagaRecoveryError].
+///
+/// See [ProcessManager] for an example.
+pub struct SagaRunner<P: ProcessManager> {
+    process_manager: Arc<P>,
+    saga_store: Arc<dyn SagaStore<P::Key, P::State>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<P: ProcessManager> SagaRunner<P> {
+    /// Creates a new [SagaRunner].
+    ///
+    /// Timeouts are checked against the [SystemClock].
+    pub fn new(process_manager: Arc<P>, saga_store: Arc<dyn SagaStore<P::Key, P::State>>) -> Self {
+        Self {
+            process_manager,
+            saga_store,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Sets the [Clock] used for checking timeouts.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Handles an incoming event, returning the saga it was correlated with, if any.
+    ///
+    /// Events correlated with sagas that are no longer running are ignored. Should handling the
+    /// event fail, the saga is aborted and the error is returned.
+    pub async fn handle(&self, event: P::Event) -> crate::Result<Option<Saga<P::Key, P::State>>> {
+        let key = match self.process_manager.correlate(&event) {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let mut saga = match self.saga_store.load(&key).await? {
+            Some(saga) => saga,
+            None => match self.process_manager.start(&event) {
+                Some(state) => Saga::new(key, state),
+                None => return Ok(None),
+            },
+        };
+
+        if !saga.is_running() {
+            return Ok(Some(saga));
+        }
+
+        if let Err(e) = self.process_manager.handle(&mut saga, event).await {
+            saga.abort();
+
+            return Err(recovering(e, self.save(saga).await));
+        }
+
+        self.save(saga).await.map(Some)
+    }
+
+    /// Handles the timeout of every running saga past its deadline, returning the result of each.
+    ///
+    /// Failing to handle the timeout of a saga does not prevent the others from being handled.
+    pub async fn check_timeouts(
+        &self,
+    ) -> crate::Result<Vec<crate::Result<Saga<P::Key, P::State>>>> {
+        let expired = self.saga_store.find_expired(self.clock.now()).await?;
+
+        let mut results = Vec::with_capacity(expired.len());
+
+        for mut saga in expired {
+            let result = match self.process_manager.on_timeout(&mut saga).await {
+                Ok(()) => self.save(saga).await,
+                Err(e) => {
+                    saga.abort();
+
+                    Err(recovering(e, self.save(saga).await))
+                }
+            };
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    async fn save(
+        &self,
+        mut saga: Saga<P::Key, P::State>,
+    ) -> crate::Result<Saga<P::Key, P::State>> {
+        if saga.status == SagaStatus::Compensating {
+            if let Err(e) = self.process_manager.compensate(&mut saga).await {
+                saga.clear_deadline();
+
+                return Err(recovering(e, self.saga_store.save(saga).await));
+            }
+
+            saga.status = SagaStatus::Compensated;
+        }
+
+        if !saga.is_running() {
+            saga.clear_deadline();
+        }
+
+        self.saga_store.save(saga).await
+    }
+}
+
+/// Error returned by the [SagaRunner] when recovering from an error, by compensating or saving the
+/// aborted [Saga], fails as well.
+///
+/// The original error is the [source](std::error::Error::source) of this one.
+#[derive(Debug)]
+pub struct SagaRecoveryError {
+    /// Original error.
+    pub error: BoxError,
+    /// Error that occurred while recovering from the original one.
+    pub recovery_error: BoxError,
+}
+
+impl std::fmt::Display for SagaRecoveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (recovering from it also failed: {})",
+            self.error, self.recovery_error
+        )
+    }
+}
+
+impl std::error::Error for SagaRecoveryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+/// Returns the original error, along with the recovery error, if recovering from it failed.
+fn recovering<T>(error: BoxError, recovery: crate::Result<T>) -> BoxError {
+    match recovery {
+        Ok(_) => error,
+        Err(recovery_error) => SagaRecoveryError {
+            error,
+            recovery_error,
+        }
+        .into(),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::AggregateRootEx;
+use crate::domain::{AggregateRootEx, DomainEventEnvelope, Entity};
 
 /// Trait for representing a **Request**.
 ///
@@ -162,5 +162,9 @@ pub use RequestHandler as QueryHandler;
 #[async_trait::async_trait]
 pub trait DomainEventHandler<T: AggregateRootEx>: Send + Sync {
     /// Handles the incoming domain event, applying any necessary changes to the entity.
-    async fn handle(&self, entity: T, event: T::DomainEvent) -> crate::Result<T>;
+    async fn handle(
+        &self,
+        entity: T,
+        envelope: DomainEventEnvelope<T::DomainEvent, <T as Entity>::Id>,
+    ) -> crate::Result<T>;
 }
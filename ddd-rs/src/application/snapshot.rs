@@ -0,0 +1,40 @@
+use crate::domain::{Entity, EventSourced};
+
+/// Trait for representing a **Snapshot Store**, which persists the latest snapshot of each
+/// [EventSourced] aggregate.
+///
+/// Snapshots are the aggregates themselves, as of their [version](crate::domain::Versioned), so
+/// that only the events after it need to be replayed when loading them.
+///
+/// See [EventSourcedRepository](super::EventSourcedRepository) for an example.
+#[async_trait::async_trait]
+pub trait SnapshotStore<T: EventSourced>: Send + Sync {
+    /// Saves a snapshot of the aggregate, replacing the previous one.
+    async fn save(&self, snapshot: T) -> crate::Result<()>;
+
+    /// Loads the latest snapshot of the aggregate with the given ID.
+    async fn load(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>>;
+}
+
+/// Policy for deciding when to take snapshots of [EventSourced] aggregates.
+///
+/// See [EventSourcedRepository](super::EventSourcedRepository) for an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotPolicy {
+    /// Takes a snapshot whenever the aggregate's version reaches a multiple of the given number of
+    /// events.
+    EveryNEvents(u64),
+    /// Only takes snapshots when explicitly requested.
+    OnDemand,
+}
+
+impl SnapshotPolicy {
+    /// Checks whether a snapshot should be taken after the aggregate's version moved from
+    /// `previous_version` to `version`.
+    pub fn should_snapshot(&self, previous_version: u64, version: u64) -> bool {
+        match *self {
+            Self::EveryNEvents(0) | Self::OnDemand => false,
+            Self::EveryNEvents(n) => version / n > previous_version / n,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::domain::{AggregateRoot, Entity};
+
+use super::{Repository, RepositoryError};
+
+/// Trait for representing a [Repository] of [SoftDeletable](crate::domain::SoftDeletable)
+/// aggregates, whose deletion only marks them as deleted.
+///
+/// Deleted aggregates are hidden from every [ReadRepository](super::ReadRepository) operation, and
+/// cannot be updated nor deleted again until they are restored.
+///
+/// See [SoftDeletable](crate::domain::SoftDeletable) for an example.
+#[async_trait::async_trait]
+pub trait SoftDeleteRepository<T: AggregateRoot>: Repository<T> {
+    /// Returns the entity with the given ID, if it exists, whether it is deleted or not.
+    async fn get_by_id_including_deleted(
+        &self,
+        id: <T as Entity>::Id,
+    ) -> crate::Result<Option<T>, RepositoryError>;
+
+    /// Restores the deleted entity with the given ID, returning it.
+    ///
+    /// Entities that are not deleted are returned as they are.
+    async fn restore(&self, id: <T as Entity>::Id) -> crate::Result<T, RepositoryError>;
+
+    /// Returns a paginated list of entities, including the deleted ones.
+    async fn list_including_deleted(
+        &self,
+        skip: usize,
+        take: usize,
+    ) -> crate::Result<Vec<T>, RepositoryError>;
+
+    /// Permanently deletes the entity with the given ID, whether it is deleted or not.
+    async fn purge(&self, id: <T as Entity>::Id) -> crate::Result<(), RepositoryError>;
+}
@@ -0,0 +1,284 @@
+use std::sync::Arc;
+
+use crate::domain::{Entity, Specification, TenantScoped};
+
+use super::{ReadRepository, Repository, RepositoryError, SoftDeleteRepository};
+
+/// A [Repository] of [TenantScoped] aggregates, scoped to a single tenant.
+///
+/// Aggregates of other tenants are hidden from every read operation, and updating or deleting one
+/// fails with [RepositoryError::NotFound], as if it did not exist. Writing an aggregate of another
+/// tenant than the repository's fails with [RepositoryError::CrossTenant].
+///
+/// IDs are still unique across tenants, hence adding an aggregate whose ID belongs to another
+/// tenant fails with [RepositoryError::AlreadyExists], which does disclose that the ID is taken.
+/// Prefer IDs that cannot be guessed (e.g. random UUIDs) where this matters.
+///
+/// When the underlying repository is a [SoftDeleteRepository], so is the [TenantRepository], with
+/// deleted aggregates of other tenants being hidden and reported as [RepositoryError::NotFound]
+/// the same way.
+///
+/// The stored aggregate's tenant is checked before writing it, but not atomically, hence the
+/// underlying repository should not allow aggregates to change tenants concurrently (e.g. by
+/// rejecting stale [Versioned](crate::domain::Versioned) aggregates).
+///
+/// Wrapping the repository is cheap, hence a [TenantRepository] is usually created per request,
+/// for the tenant of the current user.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ddd_rs::{
+///     application::{ReadRepository, Repository, RepositoryError, TenantRepository},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Invoice {
+///     #[entity(id)]
+///     id: u32,
+///     #[aggregate_root(tenant)]
+///     tenant_id: &'static str,
+///     amount: u64,
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let repository = Arc::new(InMemoryRepository::new());
+///
+/// let acme = TenantRepository::new(repository.clone(), "acme");
+/// let globex = TenantRepository::new(repository.clone(), "globex");
+///
+/// acme.add(Invoice { id: 1, tenant_id: "acme", amount: 100 }).await.unwrap();
+/// acme.add(Invoice { id: 2, tenant_id: "acme", amount: 200 }).await.unwrap();
+/// globex.add(Invoice { id: 3, tenant_id: "globex", amount: 300 }).await.unwrap();
+///
+/// // Reads only see the aggregates of the current tenant.
+/// assert_eq!(acme.count().await.unwrap(), 2);
+/// assert_eq!(globex.list(0, 10).await.unwrap().len(), 1);
+/// assert!(globex.get_by_id(1).await.unwrap().is_none());
+///
+/// // Writes across tenants fail.
+/// assert!(matches!(
+///     globex.add(Invoice { id: 4, tenant_id: "acme", amount: 400 }).await,
+///     Err(RepositoryError::CrossTenant)
+/// ));
+///
+/// // Aggregates of other tenants cannot be overwritten, without disclosing their existence.
+/// assert!(matches!(
+///     globex.update(Invoice { id: 1, tenant_id: "globex", amount: 0 }).await,
+///     Err(RepositoryError::NotFound)
+/// ));
+///
+/// assert_eq!(repository.get_by_id(1).await.unwrap().unwrap().amount, 100);
+///
+/// // IDs are unique across tenants, hence adding a taken one fails regardless.
+/// assert!(matches!(
+///     globex.add(Invoice { id: 1, tenant_id: "globex", amount: 0 }).await,
+///     Err(RepositoryError::AlreadyExists)
+/// ));
+/// # })
+/// ```
+///
+/// Soft-deleted aggregates are scoped the same way:
+///
+/// ```
+/// use std::{sync::Arc, time::SystemTime};
+///
+/// use ddd_rs::{
+///     application::{Repository, RepositoryError, SoftDeleteRepository, TenantRepository},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Invoice {
+///     #[entity(id)]
+///     id: u32,
+///     #[aggregate_root(tenant)]
+///     tenant_id: &'static str,
+///     #[aggregate_root(soft_delete)]
+///     deleted_at: Option<SystemTime>,
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let repository = Arc::new(InMemoryRepository::new());
+///
+/// let acme = TenantRepository::new(repository.clone(), "acme");
+/// let globex = TenantRepository::new(repository.clone(), "globex");
+///
+/// let invoice = acme.add(Invoice { id: 1, tenant_id: "acme", deleted_at: None }).await.unwrap();
+///
+/// acme.delete(invoice).await.unwrap();
+///
+/// assert_eq!(acme.list_including_deleted(0, 10).await.unwrap().len(), 1);
+/// assert!(globex.list_including_deleted(0, 10).await.unwrap().is_empty());
+///
+/// // Deleted aggregates of other tenants can neither be restored nor purged.
+/// assert!(matches!(globex.restore(1).await, Err(RepositoryError::NotFound)));
+/// assert!(matches!(globex.purge(1).await, Err(RepositoryError::NotFound)));
+///
+/// assert!(acme.restore(1).await.unwrap().deleted_at.is_none());
+/// # })
+/// ```
+pub struct TenantRepository<T: TenantScoped, R: Repository<T> + ?Sized = dyn Repository<T>> {
+    repository: Arc<R>,
+    tenant_id: T::TenantId,
+}
+
+impl<T: TenantScoped, R: Repository<T> + ?Sized> TenantRepository<T, R> {
+    /// Creates a new [TenantRepository], scoped to the given tenant.
+    pub fn new(repository: Arc<R>, tenant_id: T::TenantId) -> Self {
+        Self {
+            repository,
+            tenant_id,
+        }
+    }
+
+    /// Identity of the tenant the repository is scoped to.
+    pub fn tenant_id(&self) -> &T::TenantId {
+        &self.tenant_id
+    }
+
+    fn is_owned(&self, entity: &T) -> bool {
+        *entity.tenant_id() == self.tenant_id
+    }
+
+    fn check_owned(&self, entity: &T) -> crate::Result<(), RepositoryError> {
+        if self.is_owned(entity) {
+            Ok(())
+        } else {
+            Err(RepositoryError::CrossTenant)
+        }
+    }
+
+    /// Checks that both the given entity and the stored one, if any, belong to the tenant, failing
+    /// with [RepositoryError::NotFound] in the latter case.
+    async fn check_tenant(&self, entity: &T) -> crate::Result<(), RepositoryError> {
+        self.check_owned(entity)?;
+
+        match self.repository.get_by_id(entity.id().clone()).await? {
+            Some(stored) if !self.is_owned(&stored) => Err(RepositoryError::NotFound),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: TenantScoped, R: Repository<T> + ?Sized> ReadRepository<T> for TenantRepository<T, R> {
+    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>, RepositoryError> {
+        let entity = self.repository.get_by_id(id).await?;
+
+        Ok(entity.filter(|e| self.is_owned(e)))
+    }
+
+    async fn list(&self, skip: usize, take: usize) -> crate::Result<Vec<T>, RepositoryError> {
+        let is_owned = |e: &T| self.is_owned(e);
+
+        let entities = self.repository.find(&is_owned).await?;
+
+        Ok(entities.into_iter().skip(skip).take(take).collect())
+    }
+
+    async fn count(&self) -> crate::Result<usize, RepositoryError> {
+        let is_owned = |e: &T| self.is_owned(e);
+
+        self.repository.count_by(&is_owned).await
+    }
+
+    async fn find(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Vec<T>, RepositoryError> {
+        let is_satisfied_by = |e: &T| self.is_owned(e) && specification.is_satisfied_by(e);
+
+        self.repository.find(&is_satisfied_by).await
+    }
+
+    async fn find_one(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Option<T>, RepositoryError> {
+        let is_satisfied_by = |e: &T| self.is_owned(e) && specification.is_satisfied_by(e);
+
+        self.repository.find_one(&is_satisfied_by).await
+    }
+
+    async fn count_by(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<usize, RepositoryError> {
+        let is_satisfied_by = |e: &T| self.is_owned(e) && specification.is_satisfied_by(e);
+
+        self.repository.count_by(&is_satisfied_by).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: TenantScoped, R: Repository<T> + ?Sized> Repository<T> for TenantRepository<T, R> {
+    async fn add(&self, entity: T) -> crate::Result<T, RepositoryError> {
+        self.check_owned(&entity)?;
+
+        self.repository.add(entity).await
+    }
+
+    async fn update(&self, entity: T) -> crate::Result<T, RepositoryError> {
+        self.check_tenant(&entity).await?;
+
+        self.repository.update(entity).await
+    }
+
+    async fn delete(&self, entity: T) -> crate::Result<(), RepositoryError> {
+        self.check_tenant(&entity).await?;
+
+        self.repository.delete(entity).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: TenantScoped, R: SoftDeleteRepository<T> + ?Sized> SoftDeleteRepository<T>
+    for TenantRepository<T, R>
+{
+    async fn get_by_id_including_deleted(
+        &self,
+        id: <T as Entity>::Id,
+    ) -> crate::Result<Option<T>, RepositoryError> {
+        let entity = self.repository.get_by_id_including_deleted(id).await?;
+
+        Ok(entity.filter(|e| self.is_owned(e)))
+    }
+
+    async fn restore(&self, id: <T as Entity>::Id) -> crate::Result<T, RepositoryError> {
+        self.get_by_id_including_deleted(id.clone())
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+
+        self.repository.restore(id).await
+    }
+
+    async fn list_including_deleted(
+        &self,
+        skip: usize,
+        take: usize,
+    ) -> crate::Result<Vec<T>, RepositoryError> {
+        let entities = self
+            .repository
+            .list_including_deleted(0, usize::MAX)
+            .await?;
+
+        Ok(entities
+            .into_iter()
+            .filter(|e| self.is_owned(e))
+            .skip(skip)
+            .take(take)
+            .collect())
+    }
+
+    async fn purge(&self, id: <T as Entity>::Id) -> crate::Result<(), RepositoryError> {
+        self.get_by_id_including_deleted(id.clone())
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+
+        self.repository.purge(id).await
+    }
+}
@@ -39,7 +39,171 @@
 ///     bar: u32,
 /// }
 /// ```
-pub trait AggregateRoot: super::Entity + Send + Sync + 'static {}
+pub trait AggregateRoot: super::Entity + Send + Sync + 'static {
+    /// Returns the aggregate as [Versioned], if it supports optimistic concurrency control.
+    ///
+    /// This is automatically implemented by the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+    /// macro when using the `#[aggregate_root(version)]` attribute.
+    fn as_versioned(&self) -> Option<&dyn Versioned> {
+        None
+    }
+
+    /// Mutable counterpart of [as_versioned](AggregateRoot::as_versioned).
+    fn as_versioned_mut(&mut self) -> Option<&mut dyn Versioned> {
+        None
+    }
+
+    /// Returns the aggregate as [Invariants](super::Invariants), if it has invariants to be checked
+    /// before being persisted.
+    ///
+    /// This is automatically implemented by the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+    /// macro, either when using the `#[aggregate_root(invariant = ...)]` attribute or when the
+    /// aggregate has a hand-written implementation of [Invariants](super::Invariants). Manual
+    /// implementations of this trait must override it, since repositories would not check the
+    /// invariants otherwise.
+    fn as_invariants(&self) -> Option<&dyn super::Invariants> {
+        None
+    }
+
+    /// Returns the aggregate as [ChangeTracked](super::ChangeTracked), if it records which of its
+    /// fields were modified.
+    ///
+    /// This is automatically implemented by the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+    /// macro when using the `#[aggregate_root(track_changes)]` attribute.
+    fn as_change_tracked(&self) -> Option<&dyn super::ChangeTracked> {
+        None
+    }
+
+    /// Mutable counterpart of [as_change_tracked](AggregateRoot::as_change_tracked).
+    fn as_change_tracked_mut(&mut self) -> Option<&mut dyn super::ChangeTracked> {
+        None
+    }
+
+    /// Clears the changes recorded by the aggregate's [EntityCollection](super::EntityCollection)s,
+    /// once it is persisted.
+    ///
+    /// This is automatically implemented by the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+    /// macro for every [EntityCollection](super::EntityCollection) field.
+    fn clear_collection_changes(&mut self) {}
+
+    /// Number of changes to be persisted, by which repositories advance the version of
+    /// [Versioned] aggregates.
+    ///
+    /// Each pending domain event is a change, and so is a write without any, except for
+    /// [EventSourced] aggregates, which only change through their domain events.
+    ///
+    /// This is automatically implemented by the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+    /// macro when using the `#[aggregate_root(domain_events)]` attribute.
+    fn pending_changes(&self) -> u64 {
+        1
+    }
+
+    /// Discards the domain events registered by the aggregate since it was last persisted.
+    ///
+    /// Repositories that store the aggregate as a whole use it, so that the stored aggregate has no
+    /// pending domain events.
+    ///
+    /// This is automatically implemented by the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+    /// macro when using the `#[aggregate_root(domain_events)]` attribute.
+    fn clear_domain_events(&mut self) {}
+
+    /// Returns the aggregate as [SoftDeletable](super::SoftDeletable), if deleting it should only
+    /// mark it as deleted.
+    ///
+    /// This is automatically implemented by the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+    /// macro when using the `#[aggregate_root(soft_delete)]` attribute.
+    fn as_soft_deletable(&self) -> Option<&dyn super::SoftDeletable> {
+        None
+    }
+
+    /// Mutable counterpart of [as_soft_deletable](AggregateRoot::as_soft_deletable).
+    fn as_soft_deletable_mut(&mut self) -> Option<&mut dyn super::SoftDeletable> {
+        None
+    }
+}
+
+/// Trait for representing a **Versioned** [AggregateRoot].
+///
+/// The version is used for optimistic concurrency control: repositories compare the version of the
+/// aggregate being persisted with the one of the stored aggregate, and reject the write with a
+/// [ConcurrencyError](crate::application::ConcurrencyError) if they differ.
+///
+/// The version counts the changes persisted to the aggregate, as reported by
+/// [pending_changes](AggregateRoot::pending_changes): each domain event is a change, and so is
+/// each write without any. [EventSourced] aggregates only change through their domain events,
+/// hence their version is the number of events applied to them.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::AggregateRoot](crate::AggregateRoot) macro and the
+/// `#[aggregate_root(version)]` attribute:
+///
+/// ```
+/// use ddd_rs::{
+///     application::{ConcurrencyError, ReadRepository, Repository, RepositoryError},
+///     domain::Versioned,
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct MyEntity {
+///     #[entity(id)]
+///     id: u32,
+///     my_field: String,
+///     #[aggregate_root(version)]
+///     version: u64,
+/// }
+///
+/// impl MyEntity {
+///     pub fn new(id: u32, my_field: impl ToString) -> Self {
+///         Self {
+///             id,
+///             my_field: my_field.to_string(),
+///             version: 0,
+///         }
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let repository: InMemoryRepository<MyEntity> = InMemoryRepository::new();
+///
+/// // The version is incremented upon each successful write without domain events.
+/// let my_entity = repository.add(MyEntity::new(1, "foo")).await.unwrap();
+///
+/// assert_eq!(my_entity.version(), 1);
+///
+/// // Two concurrent handlers load the same version of the aggregate.
+/// let mut a = repository.get_by_id(1).await.unwrap().unwrap();
+/// let mut b = repository.get_by_id(1).await.unwrap().unwrap();
+///
+/// a.my_field = "bar".to_string();
+/// b.my_field = "baz".to_string();
+///
+/// // The first write succeeds, while the second one is rejected, instead of silently overwriting
+/// // the changes made by the first.
+/// let a = repository.update(a).await.unwrap();
+///
+/// assert_eq!(a.version(), 2);
+///
+/// let error = repository.update(b).await.err().unwrap();
+///
+/// assert!(matches!(
+///     error,
+///     RepositoryError::Concurrency(ConcurrencyError { expected: 1, actual: 2 })
+/// ));
+///
+/// let my_entity = repository.get_by_id(1).await.unwrap().unwrap();
+///
+/// assert_eq!(my_entity.my_field, "bar");
+/// # })
+/// ```
+pub trait Versioned {
+    /// Version of the aggregate.
+    fn version(&self) -> u64;
+
+    /// Sets the version of the aggregate.
+    fn set_version(&mut self, version: u64);
+}
 
 /// Extensions to the [AggregateRoot] behavior.
 ///
@@ -209,3 +373,156 @@ pub trait AggregateRootEx: AggregateRoot {
     /// Clears all domain events from the aggregate, returning them in order of occurrence.
     fn take_domain_events(&mut self) -> Vec<Self::DomainEvent>;
 }
+
+/// Trait for representing an **Event-Sourced** [AggregateRoot].
+///
+/// > Event Sourcing ensures that all changes to application state are stored as a sequence of
+/// > events. Not just can we query these events, we can also use the event log to reconstruct
+/// > past states.
+///
+/// The aggregate's state is only ever mutated by [applying](EventSourced::apply) its own domain
+/// events, which allows it to be rebuilt purely from its history. Its [version](Versioned::version)
+/// is the number of events from its history that were applied to it.
+///
+/// # Examples
+///
+/// Derive the [AggregateRootEx] and [Versioned] implementations using the
+/// [ddd_rs::AggregateRoot](crate::AggregateRoot) macro with the `#[aggregate_root(event_sourced)]`
+/// attribute, so that the derived `register_domain_event` method both applies and records the
+/// domain event:
+///
+/// ```
+/// use ddd_rs::{
+///     application::Repository,
+///     domain::{AggregateRootEx, EventSourced, Versioned},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum AccountEvent {
+///     Opened { id: u32 },
+///     Deposited { amount: u64 },
+///     Withdrawn { amount: u64 },
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone, Default)]
+/// #[aggregate_root(event_sourced)]
+/// struct Account {
+///     #[entity(id)]
+///     id: u32,
+///     balance: u64,
+///     #[aggregate_root(version)]
+///     version: u64,
+///     #[aggregate_root(domain_events)]
+///     domain_events: Vec<AccountEvent>,
+/// }
+///
+/// // Commands only validate their input and register domain events, which are then applied to
+/// // the aggregate in order to mutate its state.
+/// impl Account {
+///     pub fn open(id: u32) -> Self {
+///         let mut account = Self::default();
+///
+///         account.register_domain_event(AccountEvent::Opened { id });
+///
+///         account
+///     }
+///
+///     pub fn deposit(&mut self, amount: u64) {
+///         self.register_domain_event(AccountEvent::Deposited { amount });
+///     }
+///
+///     pub fn withdraw(&mut self, amount: u64) -> Result<(), &'static str> {
+///         if amount > self.balance {
+///             return Err("Insufficient funds");
+///         }
+///
+///         self.register_domain_event(AccountEvent::Withdrawn { amount });
+///
+///         Ok(())
+///     }
+/// }
+///
+/// impl EventSourced for Account {
+///     fn apply(&mut self, event: &AccountEvent) {
+///         match event {
+///             AccountEvent::Opened { id } => self.id = *id,
+///             AccountEvent::Deposited { amount } => self.balance += amount,
+///             AccountEvent::Withdrawn { amount } => self.balance -= amount,
+///         }
+///     }
+/// }
+///
+/// let mut account = Account::open(42);
+///
+/// account.deposit(100);
+/// account.withdraw(30).unwrap();
+///
+/// assert!(account.withdraw(100).is_err());
+/// assert_eq!(account.balance, 70);
+///
+/// // Registered events are not part of the aggregate's history until they are persisted, hence
+/// // its version remains the same.
+/// assert_eq!(account.version(), 0);
+///
+/// let history = account.take_domain_events();
+///
+/// assert_eq!(history.len(), 3);
+///
+/// // Rebuild the aggregate from its history.
+/// let account = Account::rehydrate(history);
+///
+/// assert_eq!(account.id, 42);
+/// assert_eq!(account.balance, 70);
+/// assert_eq!(account.version(), 3);
+/// assert!(account.domain_events.is_empty());
+///
+/// // Repositories advance the version by the number of events persisted.
+/// # tokio_test::block_on(async {
+/// let repository = InMemoryRepository::new();
+///
+/// let mut account = repository.add(account).await.unwrap();
+///
+/// assert_eq!(account.version(), 3);
+///
+/// account.deposit(10);
+/// account.deposit(20);
+///
+/// let account = repository.update(account).await.unwrap();
+///
+/// assert_eq!(account.version(), 5);
+/// assert_eq!(account.balance, 100);
+/// # })
+/// ```
+pub trait EventSourced: AggregateRootEx + Versioned {
+    /// Applies the domain event to the aggregate, mutating its state.
+    ///
+    /// Domain events represent facts that already happened, so this method must not fail.
+    fn apply(&mut self, event: &Self::DomainEvent);
+
+    /// Applies the given events from the aggregate's history, in order, incrementing its version
+    /// accordingly.
+    fn replay<I>(&mut self, events: I)
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Self::DomainEvent>,
+    {
+        for event in events {
+            self.apply(&event);
+            self.set_version(self.version() + 1);
+        }
+    }
+
+    /// Rebuilds the aggregate from its default state by replaying its whole history.
+    fn rehydrate<I>(events: I) -> Self
+    where
+        Self: Default,
+        I: IntoIterator<Item = Self::DomainEvent>,
+    {
+        let mut aggregate = Self::default();
+
+        aggregate.replay(events);
+
+        aggregate
+    }
+}
@@ -0,0 +1,114 @@
+/// Set of the fields of an [AggregateRoot](super::AggregateRoot) modified since it was last
+/// persisted, in order of first modification.
+///
+/// See [ChangeTracked] for an example.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeSet {
+    fields: Vec<&'static str>,
+}
+
+impl ChangeSet {
+    /// Creates a new, empty [ChangeSet].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a modification of the given field.
+    pub fn record(&mut self, field: &'static str) {
+        if !self.fields.contains(&field) {
+            self.fields.push(field);
+        }
+    }
+
+    /// Checks whether the given field was modified.
+    pub fn contains(&self, field: &str) -> bool {
+        self.fields.contains(&field)
+    }
+
+    /// Names of the modified fields, in order of first modification.
+    pub fn fields(&self) -> &[&'static str] {
+        &self.fields
+    }
+
+    /// Checks if no field was modified.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Clears the recorded modifications.
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+}
+
+/// Trait for representing a **Change-Tracked** [AggregateRoot](super::AggregateRoot).
+///
+/// The aggregate records which of its fields were modified, so that repositories may persist only
+/// those fields, or audit them. Repositories clear the changes once the aggregate is persisted.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::AggregateRoot](crate::AggregateRoot) macro with
+/// the `#[aggregate_root(track_changes)]` attribute, along with a [ChangeSet] field tagged with
+/// `#[aggregate_root(changes)]`. A `set_<field>` setter is then derived for every field other than
+/// the identity, domain events, version and changes fields, which records the modification:
+///
+/// ```
+/// use ddd_rs::{
+///     application::{ReadRepository, Repository},
+///     domain::{ChangeSet, ChangeTracked},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// #[aggregate_root(track_changes)]
+/// struct Customer {
+///     #[entity(id)]
+///     id: u32,
+///     name: String,
+///     email: String,
+///     #[aggregate_root(changes)]
+///     changes: ChangeSet,
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let repository = InMemoryRepository::new();
+///
+/// let mut customer = Customer {
+///     id: 1,
+///     name: "John Doe".to_string(),
+///     email: "john@example.com".to_string(),
+///     changes: ChangeSet::new(),
+/// };
+///
+/// customer.set_email("john.doe@example.com");
+/// customer.set_name("John");
+/// customer.set_email("jdoe@example.com");
+///
+/// assert_eq!(customer.changes().fields(), ["email", "name"]);
+///
+/// // Changes are cleared once the aggregate is persisted.
+/// let mut customer = repository.add(customer).await.unwrap();
+///
+/// assert!(customer.changes().is_empty());
+///
+/// customer.set_name("Jane Doe");
+///
+/// assert!(customer.changes().contains("name"));
+/// assert!(!customer.changes().contains("email"));
+///
+/// repository.update(customer).await.unwrap();
+///
+/// let customer = repository.get_by_id(1).await.unwrap().unwrap();
+///
+/// assert_eq!(customer.name, "Jane Doe");
+/// assert!(customer.changes().is_empty());
+/// # })
+/// ```
+pub trait ChangeTracked {
+    /// Fields modified since the changes were last cleared.
+    fn changes(&self) -> &ChangeSet;
+
+    /// Clears the tracked changes, usually after persisting them.
+    fn clear_changes(&mut self);
+}
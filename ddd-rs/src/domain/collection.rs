@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Entity;
+
+/// An ordered collection of child [Entities](Entity) within an
+/// [AggregateRoot](super::AggregateRoot), keyed by their identity.
+///
+/// Besides enforcing the uniqueness of its entities' identities, the collection records which of
+/// them were added, modified or removed, so that repositories can persist only the children that
+/// changed. Repositories clear these changes once the aggregate is persisted, through
+/// [AggregateRoot::clear_collection_changes](super::AggregateRoot::clear_collection_changes).
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::{
+///     application::Repository,
+///     domain::{Entity, EntityCollection},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::Entity, Clone, Debug)]
+/// struct OrderLine {
+///     #[entity(id)]
+///     line_no: u16,
+///     quantity: u32,
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Order {
+///     #[entity(id)]
+///     id: u32,
+///     lines: EntityCollection<OrderLine>,
+/// }
+///
+/// // Entities loaded from the repository are not tracked as changes.
+/// let mut order = Order {
+///     id: 1,
+///     lines: EntityCollection::from_persisted([
+///         OrderLine { line_no: 1, quantity: 2 },
+///         OrderLine { line_no: 2, quantity: 1 },
+///     ])
+///     .unwrap(),
+/// };
+///
+/// assert!(!order.lines.has_changes());
+///
+/// // Identities are unique within the collection.
+/// let duplicate = order.lines.add(OrderLine { line_no: 1, quantity: 5 }).unwrap_err();
+///
+/// assert_eq!(duplicate.0.quantity, 5);
+///
+/// order.lines.add(OrderLine { line_no: 3, quantity: 4 }).unwrap();
+/// order.lines.get_mut(&1).unwrap().quantity += 1;
+/// order.lines.remove(&2);
+///
+/// assert_eq!(order.lines.get(&1).map(|l| l.quantity), Some(3));
+/// assert_eq!(order.lines.iter().map(|l| *l.id()).collect::<Vec<_>>(), [1, 3]);
+///
+/// assert_eq!(order.lines.added(), [3]);
+/// assert_eq!(order.lines.modified(), [1]);
+/// assert_eq!(order.lines.removed(), [2]);
+///
+/// // Changing the identity of an entity moves it to its new identity.
+/// order.lines.get_mut(&3).unwrap().line_no = 4;
+///
+/// assert!(!order.lines.contains(&3));
+/// assert_eq!(order.lines.added(), [4]);
+///
+/// // Repositories clear the changes once the aggregate is persisted.
+/// # tokio_test::block_on(async {
+/// let repository = InMemoryRepository::new();
+///
+/// let order = repository.add(order).await.unwrap();
+///
+/// assert!(!order.lines.has_changes());
+/// # })
+/// ```
+#[derive(Debug, Clone)]
+pub struct EntityCollection<T: Entity> {
+    entities: Vec<T>,
+    index: HashMap<T::Id, usize>,
+    added: Vec<T::Id>,
+    modified: Vec<T::Id>,
+    removed: Vec<T::Id>,
+}
+
+impl<T: Entity> EntityCollection<T>
+where
+    T::Id: Hash + Eq,
+{
+    /// Creates a new, empty [EntityCollection].
+    pub fn new() -> Self {
+        Self {
+            entities: Vec::new(),
+            index: HashMap::new(),
+            added: Vec::new(),
+            modified: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Creates a new [EntityCollection] from already persisted entities, which are not tracked as
+    /// added.
+    pub fn from_persisted(
+        entities: impl IntoIterator<Item = T>,
+    ) -> Result<Self, DuplicateEntity<T>> {
+        let mut collection = Self::new();
+
+        for entity in entities {
+            collection.add(entity)?;
+        }
+
+        collection.clear_changes();
+
+        Ok(collection)
+    }
+
+    /// Adds an entity to the end of the collection, failing if there is already an entity with the
+    /// same identity.
+    pub fn add(&mut self, entity: T) -> Result<(), DuplicateEntity<T>> {
+        let id = entity.id().clone();
+
+        if self.index.contains_key(&id) {
+            return Err(DuplicateEntity(entity));
+        }
+
+        self.index.insert(id.clone(), self.entities.len());
+        self.entities.push(entity);
+
+        // Entities removed then added back were only modified, as far as persistence goes.
+        if let Some(i) = self.removed.iter().position(|removed| *removed == id) {
+            self.removed.remove(i);
+            track(&mut self.modified, id);
+        } else {
+            track(&mut self.added, id);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the entity with the given identity.
+    pub fn get(&self, id: &T::Id) -> Option<&T> {
+        self.index.get(id).map(|&i| &self.entities[i])
+    }
+
+    /// Gets the entity with the given identity for modification, tracking it as modified once the
+    /// returned guard is dropped.
+    ///
+    /// If the entity's identity was changed through the guard, the entity is moved to its new
+    /// identity instead, as if it was [removed](Self::remove) then [replaced](Self::replace).
+    pub fn get_mut(&mut self, id: &T::Id) -> Option<EntityMut<'_, T>> {
+        let index = *self.index.get(id)?;
+
+        Some(EntityMut {
+            id: id.clone(),
+            index,
+            collection: self,
+        })
+    }
+
+    /// Replaces the entity with the same identity, returning the previous one, or adds it if there
+    /// is none.
+    pub fn replace(&mut self, entity: T) -> Option<T> {
+        let id = entity.id().clone();
+
+        match self.index.get(&id) {
+            Some(&i) => {
+                self.track_modified(id);
+
+                Some(std::mem::replace(&mut self.entities[i], entity))
+            }
+            None => {
+                self.add(entity).ok();
+
+                None
+            }
+        }
+    }
+
+    /// Removes the entity with the given identity, returning it.
+    pub fn remove(&mut self, id: &T::Id) -> Option<T> {
+        let i = self.index.remove(id)?;
+        let entity = self.entities.remove(i);
+
+        for j in self.index.values_mut().filter(|j| **j > i) {
+            *j -= 1;
+        }
+
+        self.modified.retain(|modified| modified != id);
+
+        // Entities added then removed were never persisted, hence there is nothing to remove.
+        if let Some(i) = self.added.iter().position(|added| added == id) {
+            self.added.remove(i);
+        } else {
+            track(&mut self.removed, id.clone());
+        }
+
+        Some(entity)
+    }
+
+    /// Checks whether the collection contains an entity with the given identity.
+    pub fn contains(&self, id: &T::Id) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// Number of entities in the collection.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Checks if the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Iterates over the entities, in order of addition.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.entities.iter()
+    }
+
+    fn track_modified(&mut self, id: T::Id) {
+        // Added entities are persisted as a whole, regardless of later modifications.
+        if !self.added.contains(&id) {
+            track(&mut self.modified, id);
+        }
+    }
+}
+
+impl<T: Entity> EntityCollection<T> {
+    /// Identities of the entities added since the changes were last cleared.
+    pub fn added(&self) -> &[T::Id] {
+        &self.added
+    }
+
+    /// Identities of the entities modified since the changes were last cleared.
+    pub fn modified(&self) -> &[T::Id] {
+        &self.modified
+    }
+
+    /// Identities of the entities removed since the changes were last cleared.
+    pub fn removed(&self) -> &[T::Id] {
+        &self.removed
+    }
+
+    /// Checks whether any entity was added, modified or removed since the changes were last
+    /// cleared.
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.modified.is_empty() || !self.removed.is_empty()
+    }
+
+    /// Clears the tracked changes, usually after persisting them.
+    pub fn clear_changes(&mut self) {
+        self.added.clear();
+        self.modified.clear();
+        self.removed.clear();
+    }
+}
+
+impl<T: Entity> Default for EntityCollection<T>
+where
+    T::Id: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Entity> IntoIterator for &'a EntityCollection<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entities.iter()
+    }
+}
+
+impl<T: Entity> IntoIterator for EntityCollection<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entities.into_iter()
+    }
+}
+
+/// Guard for modifying an [Entity] within an [EntityCollection], which tracks it as modified once
+/// dropped.
+///
+/// See [EntityCollection::get_mut] for details.
+pub struct EntityMut<'a, T: Entity>
+where
+    T::Id: Hash + Eq,
+{
+    collection: &'a mut EntityCollection<T>,
+    index: usize,
+    id: T::Id,
+}
+
+impl<T: Entity> std::ops::Deref for EntityMut<'_, T>
+where
+    T::Id: Hash + Eq,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.collection.entities[self.index]
+    }
+}
+
+impl<T: Entity> std::ops::DerefMut for EntityMut<'_, T>
+where
+    T::Id: Hash + Eq,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.collection.entities[self.index]
+    }
+}
+
+impl<T: Entity> Drop for EntityMut<'_, T>
+where
+    T::Id: Hash + Eq,
+{
+    fn drop(&mut self) {
+        if self.collection.entities[self.index].id() == &self.id {
+            self.collection.track_modified(self.id.clone());
+        } else if let Some(entity) = self.collection.remove(&self.id) {
+            self.collection.replace(entity);
+        }
+    }
+}
+
+/// Error returned when adding an [Entity] to an [EntityCollection] that already contains an entity
+/// with the same identity, holding the rejected entity.
+///
+/// See [EntityCollection] for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateEntity<T>(pub T);
+
+impl<T> std::fmt::Display for DuplicateEntity<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("An entity with the same identity already exists in the collection")
+    }
+}
+
+impl<T: std::fmt::Debug> std::error::Error for DuplicateEntity<T> {}
+
+fn track<Id: PartialEq>(ids: &mut Vec<Id>, id: Id) {
+    if !ids.contains(&id) {
+        ids.push(id);
+    }
+}
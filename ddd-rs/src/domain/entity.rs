@@ -40,6 +40,133 @@
 /// assert_ne!(a, c);
 /// assert_ne!(a.id(), c.id());
 /// ```
+///
+/// Tag multiple fields with the `#[entity(id)]` attribute in order to derive a composite identity,
+/// which is a tuple of all tagged fields (in order of declaration) aliased as `<Entity>Id`. It is
+/// cached in an [IdCache] field, tagged with the `#[entity(id_cache)]` attribute, so that it may be
+/// borrowed:
+///
+/// ```
+/// use ddd_rs::domain::{Entity, IdCache};
+///
+/// #[derive(ddd_rs::Entity, Debug)]
+/// struct OrderLine {
+///     #[entity(id)]
+///     order_id: u32,
+///     #[entity(id)]
+///     line_no: u16,
+///     #[entity(id_cache)]
+///     id: IdCache<OrderLineId>,
+///     sku: String,
+/// }
+///
+/// let line = |order_id, line_no, sku: &str| OrderLine {
+///     order_id,
+///     line_no,
+///     id: IdCache::new(),
+///     sku: sku.to_string(),
+/// };
+///
+/// let a = line(1, 1, "foo");
+/// let b = line(1, 1, "bar");
+/// let c = line(1, 2, "foo");
+/// let d = line(2, 1, "foo");
+///
+/// assert_eq!(a.id(), &(1, 1));
+///
+/// // Equality is based on every component of the identity.
+/// assert_eq!(a, b);
+/// assert_ne!(a, c);
+/// assert_ne!(a, d);
+///
+/// // Reset the cache whenever an identity component changes.
+/// let mut e = line(1, 1, "foo");
+///
+/// e.line_no = 2;
+/// e.id.reset();
+///
+/// assert_eq!(e, c);
+/// ```
+///
+/// Tuple structs and enums are also supported. Enum variants either tag their own identity field, or
+/// wrap a single entity to which the identity is delegated:
+///
+/// ```
+/// use ddd_rs::domain::Entity;
+///
+/// #[derive(ddd_rs::Entity)]
+/// struct Person(#[entity(id)] u32, String);
+///
+/// #[derive(ddd_rs::Entity)]
+/// struct Company {
+///     #[entity(id)]
+///     id: u32,
+///     name: String,
+/// }
+///
+/// #[derive(ddd_rs::Entity)]
+/// enum Customer {
+///     Person(Person),
+///     Company(Company),
+///     Anonymous(#[entity(id)] u32),
+/// }
+///
+/// let person = Customer::Person(Person(1, "John Doe".to_string()));
+/// let company = Customer::Company(Company { id: 2, name: "ACME".to_string() });
+/// let anonymous = Customer::Anonymous(3);
+///
+/// assert_eq!(*person.id(), 1);
+/// assert_eq!(*company.id(), 2);
+/// assert_eq!(*anonymous.id(), 3);
+/// ```
+///
+/// Use the `#[entity(hash, ord)]` attribute to also derive [Hash](std::hash::Hash), [PartialOrd] and
+/// [Ord] from the identity, consistently with equality:
+///
+/// ```
+/// use std::collections::{BTreeSet, HashSet};
+///
+/// #[derive(ddd_rs::Entity, Debug)]
+/// #[entity(hash, ord)]
+/// struct Product {
+///     #[entity(id)]
+///     id: u32,
+///     name: String,
+/// }
+///
+/// let product = |id, name: &str| Product { id, name: name.to_string() };
+///
+/// let hash_set = HashSet::from([product(1, "foo"), product(1, "bar"), product(2, "baz")]);
+///
+/// assert_eq!(hash_set.len(), 2);
+///
+/// let btree_set = BTreeSet::from([product(2, "baz"), product(1, "foo")]);
+///
+/// assert_eq!(btree_set.first().unwrap().name, "foo");
+/// ```
+///
+/// Use the `#[entity(copy)]` attribute to derive [Copy], bounded on every field being `Copy`, for
+/// entities that also implement [Clone]:
+///
+/// ```
+/// #[derive(ddd_rs::Entity, Clone, Debug)]
+/// #[entity(copy)]
+/// struct Seat<Class> {
+///     #[entity(id)]
+///     id: u32,
+///     class: Class,
+/// }
+///
+/// let seat = Seat { id: 1, class: 'A' };
+/// let copy = seat;
+///
+/// assert_eq!(seat, copy);
+///
+/// // Seats of non-`Copy` classes are only `Clone`.
+/// let seat = Seat { id: 2, class: "Business".to_string() };
+///
+/// assert_eq!(seat.clone(), seat);
+/// ```
 pub trait Entity: Eq + PartialEq {
     /// Identity type.
     type Id: Clone + PartialEq + Send + Sync;
@@ -47,3 +174,36 @@ pub trait Entity: Eq + PartialEq {
     /// Identity.
     fn id(&self) -> &Self::Id;
 }
+
+/// Cache of a composite [Entity] identity, derived from multiple `#[entity(id)]` fields.
+///
+/// The identity is built from its components on first access, then borrowed from the cache.
+/// Accessing it after one of its components changed panics, unless the cache is [reset](Self::reset)
+/// in between.
+///
+/// See [Entity] for an example.
+#[derive(Debug, Clone)]
+pub struct IdCache<Id>(std::sync::OnceLock<Id>);
+
+impl<Id> IdCache<Id> {
+    /// Creates a new, empty [IdCache].
+    pub const fn new() -> Self {
+        Self(std::sync::OnceLock::new())
+    }
+
+    /// Gets the cached identity, building it with the given closure if the cache is empty.
+    pub fn get_or_init(&self, f: impl FnOnce() -> Id) -> &Id {
+        self.0.get_or_init(f)
+    }
+
+    /// Empties the cache, so that the identity is built again on next access.
+    pub fn reset(&mut self) {
+        self.0.take();
+    }
+}
+
+impl<Id> Default for IdCache<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,184 @@
+use std::time::SystemTime;
+
+/// Trait for representing a **Domain Event**.
+///
+/// > Model information about activity in the domain as a series of discrete events. Represent each
+/// > event as a domain object.
+///
+/// Domain events are identified by a stable [event type](DomainEvent::event_type) and
+/// [schema version](DomainEvent::schema_version), so that they can be logged, routed and persisted
+/// by name, regardless of how their Rust types are named or evolve.
+///
+/// The [AggregateRootEx::DomainEvent](super::AggregateRootEx::DomainEvent) type is not required to
+/// implement this trait, so bound it explicitly wherever event names are needed.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::DomainEvent](crate::DomainEvent) macro:
+///
+/// ```
+/// use std::time::SystemTime;
+///
+/// use ddd_rs::domain::{DomainEvent, DomainEventEnvelope};
+///
+/// // Event types default to the variant names prefixed with the enum's, and schema versions to the
+/// // enum's (or `1`).
+/// #[derive(ddd_rs::DomainEvent)]
+/// #[domain_event(version = 2)]
+/// enum OrderEvent {
+///     Placed { customer_id: u32 },
+///     #[domain_event(name = "order.line_added", version = 3)]
+///     LineAdded(u32),
+///     Cancelled,
+/// }
+///
+/// #[derive(ddd_rs::DomainEvent)]
+/// #[domain_event(name = "customer.registered")]
+/// struct CustomerRegistered {
+///     customer_id: u32,
+/// }
+///
+/// assert_eq!(OrderEvent::Placed { customer_id: 1 }.event_type(), "OrderEvent.Placed");
+/// assert_eq!(OrderEvent::Placed { customer_id: 1 }.schema_version(), 2);
+/// assert_eq!(OrderEvent::LineAdded(42).event_type(), "order.line_added");
+/// assert_eq!(OrderEvent::LineAdded(42).schema_version(), 3);
+/// assert_eq!(OrderEvent::Cancelled.event_type(), "OrderEvent.Cancelled");
+///
+/// let event = CustomerRegistered { customer_id: 1 };
+///
+/// assert_eq!(event.event_type(), "customer.registered");
+/// assert_eq!(event.schema_version(), 1);
+///
+/// // Envelopes expose the event type of the events they wrap.
+/// let envelope = DomainEventEnvelope::new(1, SystemTime::now(), 7, OrderEvent::Cancelled);
+///
+/// assert_eq!(envelope.event_type(), "OrderEvent.Cancelled");
+/// assert_eq!(envelope.schema_version(), 2);
+/// ```
+pub trait DomainEvent: Send {
+    /// Stable name of the event type.
+    fn event_type(&self) -> &'static str;
+
+    /// Version of the event's schema, which should be increased whenever its shape changes.
+    fn schema_version(&self) -> u32 {
+        1
+    }
+}
+
+/// A **Domain Event** wrapped with its metadata.
+///
+/// Envelopes are built by the [RepositoryEx](crate::application::RepositoryEx) around each domain
+/// event taken from an [AggregateRootEx](super::AggregateRootEx), before being passed to its
+/// [DomainEventHandler](crate::application::DomainEventHandler).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// use ddd_rs::domain::DomainEventEnvelope;
+///
+/// let occurred_at = SystemTime::UNIX_EPOCH + Duration::from_secs(42);
+///
+/// let order_placed = DomainEventEnvelope::new(1, occurred_at, 7, "OrderPlaced")
+///     .with_aggregate_version(1)
+///     .with_sequence(1);
+///
+/// // Events caused by handling another one share its correlation ID.
+/// let payment_requested =
+///     DomainEventEnvelope::new(2, occurred_at, 9, "PaymentRequested").correlated_with(&order_placed);
+///
+/// assert_eq!(payment_requested.causation_id, Some(1));
+/// assert_eq!(payment_requested.correlation_id, Some(1));
+///
+/// let payment_confirmed = DomainEventEnvelope::new(3, occurred_at, 9, "PaymentConfirmed")
+///     .correlated_with(&payment_requested);
+///
+/// assert_eq!(payment_confirmed.causation_id, Some(2));
+/// assert_eq!(payment_confirmed.correlation_id, Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainEventEnvelope<E, Id> {
+    /// Globally unique identifier of the event.
+    pub event_id: u128,
+    /// When the event occurred.
+    pub occurred_at: SystemTime,
+    /// Identity of the aggregate that registered the event.
+    pub aggregate_id: Id,
+    /// Version of the aggregate right after the event, if it is [Versioned](super::Versioned).
+    pub aggregate_version: Option<u64>,
+    /// 1-based position of the event within the aggregate's history, i.e. the aggregate's previous
+    /// version plus the event's position among those persisted along with it.
+    ///
+    /// Aggregates that are not [Versioned](super::Versioned) do not track their history, hence
+    /// their events are only numbered among those persisted along with them.
+    pub sequence: u64,
+    /// Identifier of the event that started the chain of events this one belongs to, if any.
+    pub correlation_id: Option<u128>,
+    /// Identifier of the event whose handling caused this one, if any.
+    pub causation_id: Option<u128>,
+    /// Domain event.
+    pub event: E,
+}
+
+impl<E, Id> DomainEventEnvelope<E, Id> {
+    /// Creates a new [DomainEventEnvelope], with no version, sequence `0` and no correlation.
+    pub fn new(event_id: u128, occurred_at: SystemTime, aggregate_id: Id, event: E) -> Self {
+        Self {
+            event_id,
+            occurred_at,
+            aggregate_id,
+            aggregate_version: None,
+            sequence: 0,
+            correlation_id: None,
+            causation_id: None,
+            event,
+        }
+    }
+
+    /// Sets the version of the aggregate.
+    pub fn with_aggregate_version(mut self, aggregate_version: u64) -> Self {
+        self.aggregate_version = Some(aggregate_version);
+        self
+    }
+
+    /// Sets the position of the event within the aggregate's history.
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Marks the event as caused by the given one, inheriting its correlation ID (or using its
+    /// event ID, if it has none).
+    pub fn correlated_with<F, OtherId>(mut self, cause: &DomainEventEnvelope<F, OtherId>) -> Self {
+        self.correlation_id = Some(cause.correlation_id.unwrap_or(cause.event_id));
+        self.causation_id = Some(cause.event_id);
+        self
+    }
+
+    /// Maps the wrapped event, keeping its metadata.
+    pub fn map<F>(self, f: impl FnOnce(E) -> F) -> DomainEventEnvelope<F, Id> {
+        DomainEventEnvelope {
+            event_id: self.event_id,
+            occurred_at: self.occurred_at,
+            aggregate_id: self.aggregate_id,
+            aggregate_version: self.aggregate_version,
+            sequence: self.sequence,
+            correlation_id: self.correlation_id,
+            causation_id: self.causation_id,
+            event: f(self.event),
+        }
+    }
+}
+
+impl<E: DomainEvent, Id> DomainEventEnvelope<E, Id> {
+    /// Event type of the wrapped event.
+    pub fn event_type(&self) -> &'static str {
+        self.event.event_type()
+    }
+
+    /// Schema version of the wrapped event.
+    pub fn schema_version(&self) -> u32 {
+        self.event.schema_version()
+    }
+}
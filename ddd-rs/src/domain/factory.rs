@@ -0,0 +1,139 @@
+use super::{AggregateRoot, InvariantError};
+
+/// Trait for representing a **Factory**.
+///
+/// > Shift the responsibility for creating instances of complex objects and Aggregates to a
+/// > separate object, which may itself have no responsibility in the domain model but is still part
+/// > of the domain design. Provide an interface that encapsulates all complex assembly and that does
+/// > not require the client to reference the concrete classes of the objects being instantiated.
+///
+/// # Examples
+///
+/// Derive a builder for the aggregate root using the [ddd_rs::AggregateRoot](crate::AggregateRoot)
+/// macro and the `#[aggregate_root(builder)]` attribute, which:
+///
+/// - Assigns identities through the [IdGenerator](super::IdGenerator) given to the
+///   `#[entity(id, generator = ...)]` attribute;
+/// - Registers the domain event returned by the `#[aggregate_root(created = path::to::fn)]`
+///   function, with signature `fn(&Self) -> Self::DomainEvent`;
+/// - Requires every field to be set, except for the ones tagged with `#[aggregate_root(default)]`,
+///   and checks the aggregate's [Invariants](super::Invariants).
+///
+/// ```
+/// use ddd_rs::domain::{
+///     AggregateRootEx, Entity, Factory, FactoryError, InvariantError, InvariantViolation,
+///     SequentialIdGenerator,
+/// };
+///
+/// static ORDER_IDS: SequentialIdGenerator = SequentialIdGenerator::new();
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum OrderEvent {
+///     Placed { customer_id: u32 },
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Debug)]
+/// #[aggregate_root(builder, created = Order::placed, invariant = Order::has_lines)]
+/// struct Order {
+///     #[entity(id, generator = ORDER_IDS)]
+///     id: u64,
+///     customer_id: u32,
+///     lines: Vec<u32>,
+///     #[aggregate_root(default)]
+///     notes: Option<String>,
+///     #[aggregate_root(domain_events)]
+///     domain_events: Vec<OrderEvent>,
+/// }
+///
+/// impl Order {
+///     fn placed(&self) -> OrderEvent {
+///         OrderEvent::Placed { customer_id: self.customer_id }
+///     }
+///
+///     fn has_lines(&self) -> bool {
+///         !self.lines.is_empty()
+///     }
+/// }
+///
+/// let mut order = Order::builder().customer_id(42u32).lines(vec![100]).build().unwrap();
+///
+/// assert_eq!(*order.id(), 1);
+/// assert_eq!(order.notes, None);
+/// assert_eq!(order.take_domain_events(), [OrderEvent::Placed { customer_id: 42 }]);
+///
+/// assert_eq!(
+///     Order::builder().lines(vec![100]).build().unwrap_err(),
+///     FactoryError::MissingField("customer_id")
+/// );
+///
+/// assert_eq!(
+///     Order::builder().customer_id(42u32).lines(vec![]).build().unwrap_err(),
+///     FactoryError::Invariants(InvariantError::from(InvariantViolation::new(
+///         "has_lines",
+///         "Invariant `has_lines` does not hold"
+///     )))
+/// );
+///
+/// // Factories encapsulate the assembly of aggregates from their inputs.
+/// struct ReorderFactory;
+///
+/// impl Factory<Order> for ReorderFactory {
+///     type Input = Order;
+///
+///     fn create(&self, previous: Order) -> Result<Order, FactoryError> {
+///         Order::builder()
+///             .customer_id(previous.customer_id)
+///             .lines(previous.lines)
+///             .notes(format!("Reorder of #{}", previous.id))
+///             .build()
+///     }
+/// }
+///
+/// let reorder = ReorderFactory.create(order).unwrap();
+///
+/// // Identities are generated once every required field is set, before checking invariants.
+/// assert_eq!(*reorder.id(), 3);
+/// assert_eq!(reorder.notes.as_deref(), Some("Reorder of #1"));
+/// ```
+pub trait Factory<T: AggregateRoot>: Send + Sync {
+    /// Factory input type.
+    type Input;
+
+    /// Creates a new aggregate from the given input.
+    fn create(&self, input: Self::Input) -> Result<T, FactoryError>;
+}
+
+/// Error returned when a [Factory] (or a derived builder) fails to create an aggregate.
+///
+/// See [Factory] for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FactoryError {
+    /// A required field was not set.
+    MissingField(&'static str),
+    /// The created aggregate violates its [Invariants](super::Invariants).
+    Invariants(InvariantError),
+}
+
+impl From<InvariantError> for FactoryError {
+    fn from(error: InvariantError) -> Self {
+        Self::Invariants(error)
+    }
+}
+
+impl std::fmt::Display for FactoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "Missing required field `{}`", field),
+            Self::Invariants(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FactoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingField(_) => None,
+            Self::Invariants(error) => Some(error),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Trait for representing a strongly-typed **Identity**.
+///
+/// Wrapping raw identity values (e.g. `u64`, `String` or `Uuid`) in dedicated newtypes prevents
+/// mixing up identities of different entities, such as an `OrderId` and a `CustomerId`.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::Identity](crate::Identity) macro:
+///
+/// ```
+/// use std::collections::{BTreeSet, HashSet};
+///
+/// use ddd_rs::domain::Identity;
+///
+/// // `Display`, `FromStr`, `Hash`, `Ord` and friends are derived from the inner value.
+/// #[derive(ddd_rs::Identity, Debug)]
+/// struct OrderId(u64);
+///
+/// let order_id: OrderId = "42".parse().unwrap();
+///
+/// assert_eq!(order_id, OrderId::new(42));
+/// assert_eq!(order_id.to_string(), "42");
+/// assert_eq!(*order_id.value(), 42);
+///
+/// // Identities can be used as keys of both hashed and ordered collections.
+/// let hash_set = HashSet::from([OrderId::new(2), OrderId::new(1), OrderId::new(2)]);
+/// let btree_set = BTreeSet::from([OrderId::new(2), OrderId::new(1), OrderId::new(2)]);
+///
+/// assert_eq!(hash_set.len(), 2);
+/// assert_eq!(btree_set.into_iter().collect::<Vec<_>>(), [OrderId::new(1), OrderId::new(2)]);
+/// ```
+///
+/// Identities of different entities cannot be mixed up:
+///
+/// ```compile_fail
+/// use ddd_rs::domain::Identity;
+///
+/// #[derive(ddd_rs::Identity)]
+/// struct OrderId(u64);
+///
+/// #[derive(ddd_rs::Identity)]
+/// struct CustomerId(u64);
+///
+/// assert!(OrderId::new(42) == CustomerId::new(42));
+/// ```
+///
+/// With the `serde` feature enabled, identities are also (de)serialized as their inner value.
+pub trait Identity:
+    Clone + Eq + std::hash::Hash + Ord + std::fmt::Display + std::str::FromStr + Send + Sync + 'static
+{
+    /// Inner value type.
+    type Value;
+
+    /// Creates a new identity from its inner value.
+    fn new(value: Self::Value) -> Self;
+
+    /// Inner value.
+    fn value(&self) -> &Self::Value;
+}
+
+/// Trait for representing an **Identity Generator**.
+///
+/// # Examples
+///
+/// Use the `#[entity(id, generator = ...)]` attribute on the identity field when deriving the
+/// [ddd_rs::Entity](crate::Entity) macro, in order to derive a `generate_id` associated function
+/// for the entity:
+///
+/// ```
+/// use ddd_rs::domain::{Entity, Identity, SequentialIdGenerator};
+///
+/// #[derive(ddd_rs::Identity, Debug)]
+/// struct OrderId(u64);
+///
+/// // Stateful generators should be shared, e.g. as a `static` item.
+/// static ORDER_IDS: SequentialIdGenerator = SequentialIdGenerator::new();
+///
+/// #[derive(ddd_rs::Entity)]
+/// struct Order {
+///     #[entity(id, generator = ORDER_IDS)]
+///     id: OrderId,
+/// }
+///
+/// impl Order {
+///     pub fn new() -> Self {
+///         Self {
+///             id: Self::generate_id(),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Order::new().id(), &OrderId::new(1));
+/// assert_eq!(Order::new().id(), &OrderId::new(2));
+/// ```
+pub trait IdGenerator: Send + Sync {
+    /// Generated identity value type.
+    type Id;
+
+    /// Generates the next identity value.
+    fn next_id(&self) -> Self::Id;
+}
+
+/// An [IdGenerator] of sequential `u64` values, starting at `1` by default.
+///
+/// See [IdGenerator] for an example.
+#[derive(Debug)]
+pub struct SequentialIdGenerator {
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Creates a new [SequentialIdGenerator], starting at `1`.
+    pub const fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// Creates a new [SequentialIdGenerator], starting at the given value.
+    pub const fn starting_at(start: u64) -> Self {
+        Self {
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl Default for SequentialIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    type Id = u64;
+
+    fn next_id(&self) -> Self::Id {
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// An [IdGenerator] of globally unique, time-ordered `u128` values.
+///
+/// Values are laid out like ULIDs: a 48-bit Unix timestamp in milliseconds, followed by 80 random
+/// bits. These are drawn from the standard library's randomly keyed hasher, hence they are
+/// unpredictable enough for uniqueness, but not suitable for cryptographic purposes.
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::{IdGenerator, TimeOrderedIdGenerator};
+///
+/// let a = TimeOrderedIdGenerator.next_id();
+/// let b = TimeOrderedIdGenerator.next_id();
+///
+/// assert_ne!(a, b);
+/// assert!(a >> 80 <= b >> 80);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TimeOrderedIdGenerator;
+
+impl IdGenerator for TimeOrderedIdGenerator {
+    type Id = u128;
+
+    fn next_id(&self) -> Self::Id {
+        use std::hash::BuildHasher;
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis())
+            & ((1 << 48) - 1);
+
+        // Each `RandomState` is keyed differently, hence hashing the same value twice still yields
+        // independent random bits.
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let hash = |n| std::collections::hash_map::RandomState::new().hash_one(n) as u128;
+        let random = ((hash(n) << 64) | hash(n)) & ((1 << 80) - 1);
+
+        (timestamp << 80) | random
+    }
+}
+
+/// An [IdGenerator] of random (version 4) UUIDs.
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::{IdGenerator, UuidV4Generator};
+///
+/// let id = UuidV4Generator.next_id();
+///
+/// assert_eq!(id.get_version_num(), 4);
+/// assert_ne!(id, UuidV4Generator.next_id());
+/// ```
+#[cfg(feature = "uuid")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UuidV4Generator;
+
+#[cfg(feature = "uuid")]
+impl IdGenerator for UuidV4Generator {
+    type Id = uuid::Uuid;
+
+    fn next_id(&self) -> Self::Id {
+        uuid::Uuid::new_v4()
+    }
+}
+
+/// An [IdGenerator] of time-ordered (version 7) UUIDs.
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::{IdGenerator, UuidV7Generator};
+///
+/// let id = UuidV7Generator.next_id();
+///
+/// assert_eq!(id.get_version_num(), 7);
+/// assert_ne!(id, UuidV7Generator.next_id());
+/// ```
+#[cfg(feature = "uuid")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UuidV7Generator;
+
+#[cfg(feature = "uuid")]
+impl IdGenerator for UuidV7Generator {
+    type Id = uuid::Uuid;
+
+    fn next_id(&self) -> Self::Id {
+        uuid::Uuid::now_v7()
+    }
+}
+
+/// An [IdGenerator] of ULIDs.
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::{IdGenerator, UlidGenerator};
+///
+/// let id = UlidGenerator.next_id();
+///
+/// assert_ne!(id, UlidGenerator.next_id());
+/// ```
+#[cfg(feature = "ulid")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UlidGenerator;
+
+#[cfg(feature = "ulid")]
+impl IdGenerator for UlidGenerator {
+    type Id = ulid::Ulid;
+
+    fn next_id(&self) -> Self::Id {
+        ulid::Ulid::new()
+    }
+}
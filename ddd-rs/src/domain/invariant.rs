@@ -0,0 +1,156 @@
+/// Trait for representing the **Invariants** of an [AggregateRoot](super::AggregateRoot).
+///
+/// > Invariants, which are consistency rules that must be maintained whenever data changes, will
+/// > involve relationships between members of the Aggregate. Any rule that spans Aggregates will not
+/// > be expected to be up-to-date at all times.
+///
+/// Repositories check the invariants of an aggregate before persisting it, rejecting the write with
+/// an [InvariantError] if any of them is violated.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::AggregateRoot](crate::AggregateRoot) macro and the
+/// `#[aggregate_root(invariant = path::to::fn)]` attribute, once for each `fn(&Self) -> bool`
+/// invariant:
+///
+/// ```
+/// use ddd_rs::{
+///     application::{ReadRepository, Repository, RepositoryError},
+///     domain::{InvariantError, InvariantViolation, Invariants},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// #[aggregate_root(invariant = Order::has_lines, invariant = Order::is_within_limit)]
+/// struct Order {
+///     #[entity(id)]
+///     id: u32,
+///     lines: Vec<u32>,
+/// }
+///
+/// impl Order {
+///     const LIMIT: u32 = 1000;
+///
+///     fn has_lines(&self) -> bool {
+///         !self.lines.is_empty()
+///     }
+///
+///     fn is_within_limit(&self) -> bool {
+///         self.lines.iter().sum::<u32>() <= Self::LIMIT
+///     }
+/// }
+///
+/// let order = Order { id: 1, lines: vec![] };
+///
+/// assert_eq!(
+///     order.check_invariants(),
+///     Err(InvariantError::new(vec![InvariantViolation::new(
+///         "has_lines",
+///         "Invariant `has_lines` does not hold"
+///     )]))
+/// );
+///
+/// # tokio_test::block_on(async {
+/// let repository: InMemoryRepository<Order> = InMemoryRepository::new();
+///
+/// // Aggregates that violate their invariants are not persisted.
+/// let error = match repository.add(Order { id: 1, lines: vec![] }).await {
+///     Err(RepositoryError::InvariantViolation(error)) => error,
+///     _ => unreachable!(),
+/// };
+///
+/// assert_eq!(error.violations()[0].invariant, "has_lines");
+///
+/// let mut order = repository.add(Order { id: 1, lines: vec![100] }).await.unwrap();
+///
+/// order.lines.push(1000);
+///
+/// let error = match repository.update(order).await {
+///     Err(RepositoryError::InvariantViolation(error)) => error,
+///     _ => unreachable!(),
+/// };
+///
+/// assert_eq!(error.violations()[0].invariant, "is_within_limit");
+///
+/// let order = repository.get_by_id(1).await.unwrap().unwrap();
+///
+/// assert_eq!(order.lines, [100]);
+/// # })
+/// ```
+///
+/// Hand-written implementations of this trait are checked as well, as long as the aggregate
+/// derives [ddd_rs::AggregateRoot](crate::AggregateRoot), which exposes them through
+/// [AggregateRoot::as_invariants](super::AggregateRoot::as_invariants):
+///
+/// ```
+/// use ddd_rs::{
+///     application::{Repository, RepositoryError},
+///     domain::{InvariantError, InvariantViolation, Invariants},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Account {
+///     #[entity(id)]
+///     id: u32,
+///     balance: i64,
+/// }
+///
+/// impl Invariants for Account {
+///     fn check_invariants(&self) -> Result<(), InvariantError> {
+///         if self.balance < 0 {
+///             return Err(InvariantViolation::new("non_negative", "Negative balance").into());
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let repository: InMemoryRepository<Account> = InMemoryRepository::new();
+///
+/// assert!(matches!(
+///     repository.add(Account { id: 1, balance: -1 }).await,
+///     Err(RepositoryError::InvariantViolation(_))
+/// ));
+/// # })
+/// ```
+///
+/// Manual implementations of [AggregateRoot](super::AggregateRoot) must override
+/// [as_invariants](super::AggregateRoot::as_invariants) instead, which returns `None` by default.
+pub trait Invariants {
+    /// Checks all invariants of the aggregate, returning every violated one.
+    fn check_invariants(&self) -> Result<(), InvariantError>;
+}
+
+/// Error returned when an [AggregateRoot](super::AggregateRoot) violates its [Invariants].
+///
+/// See [Invariants] for an example.
+pub type InvariantError = super::Violations<InvariantViolation>;
+
+/// A single invariant violated by an [AggregateRoot](super::AggregateRoot).
+///
+/// See [Invariants] for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvariantViolation {
+    /// Name of the violated invariant.
+    pub invariant: &'static str,
+    /// Description of the violation.
+    pub message: String,
+}
+
+impl InvariantViolation {
+    /// Creates a new [InvariantViolation].
+    pub fn new(invariant: &'static str, message: impl ToString) -> Self {
+        Self {
+            invariant,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.message.fmt(f)
+    }
+}
@@ -1,8 +1,51 @@
 mod aggregate;
 pub use aggregate::*;
 
+mod change;
+pub use change::*;
+
+mod collection;
+pub use collection::*;
+
 mod entity;
 pub use entity::*;
 
+mod event;
+pub use event::*;
+
+mod factory;
+pub use factory::*;
+
+mod identity;
+pub use identity::*;
+
+mod invariant;
+pub use invariant::*;
+
+/// Money and currency value objects.
+#[cfg(feature = "money")]
+pub mod money;
+
+mod reference;
+pub use reference::*;
+
+mod service;
+pub use service::*;
+
+mod soft_delete;
+pub use soft_delete::*;
+
+mod specification;
+pub use specification::*;
+
+mod state_machine;
+pub use state_machine::*;
+
+mod tenant;
+pub use tenant::*;
+
 mod value_object;
 pub use value_object::*;
+
+mod violation;
+pub use violation::*;
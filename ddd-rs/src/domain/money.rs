@@ -0,0 +1,561 @@
+use std::cmp::Ordering;
+
+use super::ValueObject;
+
+/// An ISO 4217 **Currency**, along with its number of minor units (i.e. decimal places).
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::money::Currency;
+///
+/// let usd: Currency = "USD".parse().unwrap();
+///
+/// assert_eq!(usd, Currency::USD);
+/// assert_eq!(usd.numeric(), 840);
+/// assert_eq!(usd.minor_units(), 2);
+///
+/// assert_eq!(Currency::JPY.minor_units(), 0);
+/// assert_eq!(Currency::from_numeric(48), Some(Currency::BHD));
+/// assert!("XYZ".parse::<Currency>().is_err());
+///
+/// // Currencies outside of ISO 4217 may also be defined.
+/// const MILLI_BTC: Currency = Currency::new("mBTC", 0, 5);
+///
+/// assert_eq!(MILLI_BTC.to_string(), "mBTC");
+///
+/// // Their minor units must fit in an amount, though.
+/// assert!(std::panic::catch_unwind(|| Currency::new("XXX", 0, 39)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: &'static str,
+    numeric: u16,
+    minor_units: u8,
+}
+
+impl Currency {
+    /// Maximum number of minor units of a currency, so that a single major unit still fits in an
+    /// amount.
+    pub const MAX_MINOR_UNITS: u8 = 38;
+
+    /// Creates a new [Currency].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `minor_units` exceeds [MAX_MINOR_UNITS](Self::MAX_MINOR_UNITS), which fails to
+    /// compile when defining a `const` currency.
+    pub const fn new(code: &'static str, numeric: u16, minor_units: u8) -> Self {
+        assert!(
+            minor_units <= Self::MAX_MINOR_UNITS,
+            "Currencies must have at most 38 minor units"
+        );
+
+        Self {
+            code,
+            numeric,
+            minor_units,
+        }
+    }
+
+    /// Alphabetic code of the currency.
+    pub const fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Numeric code of the currency.
+    pub const fn numeric(&self) -> u16 {
+        self.numeric
+    }
+
+    /// Number of minor units (i.e. decimal places) of the currency.
+    pub const fn minor_units(&self) -> u8 {
+        self.minor_units
+    }
+
+    /// Gets the ISO 4217 currency with the given alphabetic code.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ISO_4217.iter().find(|c| c.code == code).copied()
+    }
+
+    /// Gets the ISO 4217 currency with the given numeric code.
+    pub fn from_numeric(numeric: u16) -> Option<Self> {
+        Self::ISO_4217
+            .iter()
+            .find(|c| c.numeric == numeric)
+            .copied()
+    }
+}
+
+macro_rules! currencies {
+    ($($code:ident = $numeric:literal, $minor_units:literal;)*) => {
+        impl Currency {
+            $(
+                #[doc = concat!("ISO 4217 `", stringify!($code), "` currency.")]
+                pub const $code: Self = Self::new(stringify!($code), $numeric, $minor_units);
+            )*
+
+            /// All ISO 4217 currencies.
+            pub const ISO_4217: &'static [Self] = &[$(Self::$code),*];
+        }
+    };
+}
+
+currencies! {
+    AED = 784, 2; AFN = 971, 2; ALL = 8, 2; AMD = 51, 2; AOA = 973, 2; ARS = 32, 2; AUD = 36, 2;
+    AWG = 533, 2; AZN = 944, 2; BAM = 977, 2; BBD = 52, 2; BDT = 50, 2; BGN = 975, 2; BHD = 48, 3;
+    BIF = 108, 0; BMD = 60, 2; BND = 96, 2; BOB = 68, 2; BRL = 986, 2; BSD = 44, 2; BTN = 64, 2;
+    BWP = 72, 2; BYN = 933, 2; BZD = 84, 2; CAD = 124, 2; CDF = 976, 2; CHF = 756, 2;
+    CLF = 990, 4; CLP = 152, 0; CNY = 156, 2; COP = 170, 2; CRC = 188, 2; CUP = 192, 2;
+    CVE = 132, 2; CZK = 203, 2; DJF = 262, 0; DKK = 208, 2; DOP = 214, 2; DZD = 12, 2;
+    EGP = 818, 2; ERN = 232, 2; ETB = 230, 2; EUR = 978, 2; FJD = 242, 2; FKP = 238, 2;
+    GBP = 826, 2; GEL = 981, 2; GHS = 936, 2; GIP = 292, 2; GMD = 270, 2; GNF = 324, 0;
+    GTQ = 320, 2; GYD = 328, 2; HKD = 344, 2; HNL = 340, 2; HTG = 332, 2; HUF = 348, 2;
+    IDR = 360, 2; ILS = 376, 2; INR = 356, 2; IQD = 368, 3; IRR = 364, 2; ISK = 352, 0;
+    JMD = 388, 2; JOD = 400, 3; JPY = 392, 0; KES = 404, 2; KGS = 417, 2; KHR = 116, 2;
+    KMF = 174, 0; KPW = 408, 2; KRW = 410, 0; KWD = 414, 3; KYD = 136, 2; KZT = 398, 2;
+    LAK = 418, 2; LBP = 422, 2; LKR = 144, 2; LRD = 430, 2; LSL = 426, 2; LYD = 434, 3;
+    MAD = 504, 2; MDL = 498, 2; MGA = 969, 2; MKD = 807, 2; MMK = 104, 2; MNT = 496, 2;
+    MOP = 446, 2; MRU = 929, 2; MUR = 480, 2; MVR = 462, 2; MWK = 454, 2; MXN = 484, 2;
+    MYR = 458, 2; MZN = 943, 2; NAD = 516, 2; NGN = 566, 2; NIO = 558, 2; NOK = 578, 2;
+    NPR = 524, 2; NZD = 554, 2; OMR = 512, 3; PAB = 590, 2; PEN = 604, 2; PGK = 598, 2;
+    PHP = 608, 2; PKR = 586, 2; PLN = 985, 2; PYG = 600, 0; QAR = 634, 2; RON = 946, 2;
+    RSD = 941, 2; RUB = 643, 2; RWF = 646, 0; SAR = 682, 2; SBD = 90, 2; SCR = 690, 2;
+    SDG = 938, 2; SEK = 752, 2; SGD = 702, 2; SHP = 654, 2; SLE = 925, 2; SOS = 706, 2;
+    SRD = 968, 2; SSP = 728, 2; STN = 930, 2; SVC = 222, 2; SYP = 760, 2; SZL = 748, 2;
+    THB = 764, 2; TJS = 972, 2; TMT = 934, 2; TND = 788, 3; TOP = 776, 2; TRY = 949, 2;
+    TTD = 780, 2; TWD = 901, 2; TZS = 834, 2; UAH = 980, 2; UGX = 800, 0; USD = 840, 2;
+    UYU = 858, 2; UYW = 927, 4; UZS = 860, 2; VES = 928, 2; VND = 704, 0; VUV = 548, 0;
+    WST = 882, 2; XAF = 950, 0; XCD = 951, 2; XCG = 532, 2; XOF = 952, 0; XPF = 953, 0;
+    YER = 886, 2; ZAR = 710, 2; ZMW = 967, 2; ZWG = 924, 2;
+}
+
+impl ValueObject for Currency {}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code)
+    }
+}
+
+impl std::str::FromStr for Currency {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_code(s).ok_or_else(|| MoneyError::UnknownCurrency(s.to_string()))
+    }
+}
+
+/// Mode for rounding amounts to the minor units of their [Currency].
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::money::{Currency, Money, RoundingMode};
+///
+/// let round = |s, mode| Money::parse(s, Currency::USD, mode).unwrap().amount();
+///
+/// assert_eq!(round("0.125", RoundingMode::Up), 13);
+/// assert_eq!(round("0.125", RoundingMode::Down), 12);
+/// assert_eq!(round("-0.125", RoundingMode::Ceiling), -12);
+/// assert_eq!(round("-0.125", RoundingMode::Floor), -13);
+/// assert_eq!(round("-0.125", RoundingMode::HalfUp), -13);
+/// assert_eq!(round("0.125", RoundingMode::HalfDown), 12);
+/// assert_eq!(round("0.1251", RoundingMode::HalfDown), 13);
+/// assert_eq!(round("0.125", RoundingMode::HalfEven), 12);
+/// assert_eq!(round("0.135", RoundingMode::HalfEven), 14);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Rounds away from zero.
+    Up,
+    /// Rounds towards zero.
+    Down,
+    /// Rounds towards positive infinity.
+    Ceiling,
+    /// Rounds towards negative infinity.
+    Floor,
+    /// Rounds to the nearest neighbor, or away from zero if equidistant.
+    HalfUp,
+    /// Rounds to the nearest neighbor, or towards zero if equidistant.
+    HalfDown,
+    /// Rounds to the nearest neighbor, or to the even one if equidistant (i.e. banker's rounding).
+    HalfEven,
+}
+
+/// An exact amount of **Money**, in the minor units of its [Currency].
+///
+/// Arithmetic is checked, failing on overflows and on mixing different currencies, and amounts are
+/// only ever rounded according to an explicit [RoundingMode].
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::money::{Currency, Money, MoneyError, RoundingMode};
+///
+/// let price = Money::parse("19.99", Currency::USD, RoundingMode::HalfEven).unwrap();
+/// let shipping = Money::from_minor(500, Currency::USD);
+///
+/// let total = price.checked_add(&shipping).unwrap();
+///
+/// assert_eq!(total.amount(), 2499);
+/// assert_eq!(total.to_string(), "24.99 USD");
+///
+/// // Currencies are never mixed up.
+/// assert_eq!(
+///     total.checked_add(&Money::from_minor(100, Currency::EUR)),
+///     Err(MoneyError::CurrencyMismatch { expected: Currency::USD, actual: Currency::EUR })
+/// );
+///
+/// // Amounts are rounded to the currency's minor units.
+/// let tax = total.scale(825, 10_000, RoundingMode::HalfUp).unwrap();
+///
+/// assert_eq!(tax.to_string(), "2.06 USD");
+///
+/// let yen = Money::parse("-1234.5", Currency::JPY, RoundingMode::Floor).unwrap();
+///
+/// assert_eq!(yen.to_string(), "-1235 JPY");
+///
+/// // Allocations never lose a cent.
+/// let shares = Money::from_minor(100, Currency::USD).split(3).unwrap();
+///
+/// assert_eq!(shares.iter().map(Money::amount).collect::<Vec<_>>(), [34, 33, 33]);
+///
+/// let shares = Money::from_minor(1001, Currency::USD).allocate(&[70, 20, 10]).unwrap();
+///
+/// assert_eq!(shares.iter().map(Money::amount).collect::<Vec<_>>(), [701, 200, 100]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    amount: i128,
+    currency: Currency,
+}
+
+impl Money {
+    /// Creates a new [Money] from an amount in minor units (e.g. cents).
+    pub const fn from_minor(amount: i128, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    /// Creates a new [Money] from an amount in major units (e.g. dollars).
+    pub fn from_major(amount: i128, currency: Currency) -> Result<Self, MoneyError> {
+        let amount = amount
+            .checked_mul(minor_units_per_major(currency)?)
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Self::from_minor(amount, currency))
+    }
+
+    /// Creates a new, zero [Money].
+    pub const fn zero(currency: Currency) -> Self {
+        Self::from_minor(0, currency)
+    }
+
+    /// Parses a decimal amount in major units (e.g. `"-12.345"`), rounding it to the currency's
+    /// minor units.
+    pub fn parse(s: &str, currency: Currency, rounding: RoundingMode) -> Result<Self, MoneyError> {
+        let invalid = || MoneyError::InvalidAmount(s.to_string());
+
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let mut numerator = integer
+            .chars()
+            .chain(fraction.chars())
+            .try_fold(0i128, |n, c| {
+                n.checked_mul(10)?
+                    .checked_add(c.to_digit(10).unwrap() as i128)
+            })
+            .ok_or(MoneyError::Overflow)?;
+
+        if negative {
+            numerator = -numerator;
+        }
+
+        let denominator = 10i128
+            .checked_pow(fraction.len() as u32)
+            .ok_or(MoneyError::Overflow)?;
+
+        let amount = numerator
+            .checked_mul(minor_units_per_major(currency)?)
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Self::from_minor(
+            div_round(amount, denominator, rounding)?,
+            currency,
+        ))
+    }
+
+    /// Amount in minor units (e.g. cents).
+    pub const fn amount(&self) -> i128 {
+        self.amount
+    }
+
+    /// Currency of the amount.
+    pub const fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Checks whether the amount is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.amount == 0
+    }
+
+    /// Checks whether the amount is positive.
+    pub const fn is_positive(&self) -> bool {
+        self.amount > 0
+    }
+
+    /// Checks whether the amount is negative.
+    pub const fn is_negative(&self) -> bool {
+        self.amount < 0
+    }
+
+    /// Adds another amount of the same currency.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, MoneyError> {
+        self.check_currency(other)?;
+
+        self.with_amount(self.amount.checked_add(other.amount))
+    }
+
+    /// Subtracts another amount of the same currency.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, MoneyError> {
+        self.check_currency(other)?;
+
+        self.with_amount(self.amount.checked_sub(other.amount))
+    }
+
+    /// Negates the amount.
+    pub fn checked_neg(&self) -> Result<Self, MoneyError> {
+        self.with_amount(self.amount.checked_neg())
+    }
+
+    /// Multiplies the amount by an integer factor.
+    pub fn checked_mul(&self, factor: i128) -> Result<Self, MoneyError> {
+        self.with_amount(self.amount.checked_mul(factor))
+    }
+
+    /// Multiplies the amount by the `numerator / denominator` ratio, rounding the result to the
+    /// currency's minor units.
+    pub fn scale(
+        &self,
+        numerator: i128,
+        denominator: i128,
+        rounding: RoundingMode,
+    ) -> Result<Self, MoneyError> {
+        let amount = self
+            .amount
+            .checked_mul(numerator)
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Self::from_minor(
+            div_round(amount, denominator, rounding)?,
+            self.currency,
+        ))
+    }
+
+    /// Compares the amount with another one of the same currency.
+    pub fn checked_cmp(&self, other: &Self) -> Result<Ordering, MoneyError> {
+        self.check_currency(other)?;
+
+        Ok(self.amount.cmp(&other.amount))
+    }
+
+    /// Allocates the amount among shares proportional to the given ratios, without losing any
+    /// minor unit.
+    ///
+    /// Minor units left over from rounding are distributed one by one to the shares with the
+    /// largest remainders, in order.
+    pub fn allocate(&self, ratios: &[u64]) -> Result<Vec<Self>, MoneyError> {
+        let total = ratios
+            .iter()
+            .try_fold(0i128, |total, ratio| total.checked_add(*ratio as i128))
+            .ok_or(MoneyError::Overflow)?;
+
+        if total == 0 {
+            return Err(MoneyError::InvalidRatios);
+        }
+
+        let mut shares = Vec::with_capacity(ratios.len());
+        let mut remainders = Vec::with_capacity(ratios.len());
+
+        for ratio in ratios {
+            let amount = self
+                .amount
+                .checked_mul(*ratio as i128)
+                .ok_or(MoneyError::Overflow)?;
+
+            shares.push(amount / total);
+            remainders.push((amount % total).unsigned_abs());
+        }
+
+        let left_over = self.amount - shares.iter().sum::<i128>();
+
+        let mut by_remainder = (0..ratios.len()).collect::<Vec<_>>();
+
+        by_remainder.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]));
+
+        for &i in by_remainder.iter().take(left_over.unsigned_abs() as usize) {
+            shares[i] += left_over.signum();
+        }
+
+        Ok(shares
+            .into_iter()
+            .map(|amount| Self::from_minor(amount, self.currency))
+            .collect())
+    }
+
+    /// Splits the amount into `n` equal shares, without losing any minor unit.
+    pub fn split(&self, n: usize) -> Result<Vec<Self>, MoneyError> {
+        self.allocate(&vec![1; n])
+    }
+
+    fn check_currency(&self, other: &Self) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: self.currency,
+                actual: other.currency,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn with_amount(&self, amount: Option<i128>) -> Result<Self, MoneyError> {
+        amount
+            .map(|amount| Self::from_minor(amount, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+}
+
+impl ValueObject for Money {}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let minor_units = self.currency.minor_units as usize;
+        let sign = if self.is_negative() { "-" } else { "" };
+        let amount = self.amount.unsigned_abs();
+
+        if minor_units == 0 {
+            return write!(f, "{}{} {}", sign, amount, self.currency);
+        }
+
+        let scale = 10u128.pow(minor_units as u32);
+
+        write!(
+            f,
+            "{}{}.{:0width$} {}",
+            sign,
+            amount / scale,
+            amount % scale,
+            self.currency,
+            width = minor_units
+        )
+    }
+}
+
+/// Error returned by [Money] and [Currency] operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// Amounts of different currencies were mixed.
+    CurrencyMismatch {
+        /// Currency of the left-hand side amount.
+        expected: Currency,
+        /// Currency of the right-hand side amount.
+        actual: Currency,
+    },
+    /// The result does not fit in the amount's range.
+    Overflow,
+    /// Division by zero.
+    DivisionByZero,
+    /// Allocation ratios are empty or all zero.
+    InvalidRatios,
+    /// The given string is not a valid decimal amount.
+    InvalidAmount(String),
+    /// The given string is not a known currency code.
+    UnknownCurrency(String),
+}
+
+impl std::fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CurrencyMismatch { expected, actual } => {
+                write!(f, "Expected currency {}, but found {}", expected, actual)
+            }
+            Self::Overflow => f.write_str("Amount overflow"),
+            Self::DivisionByZero => f.write_str("Division by zero"),
+            Self::InvalidRatios => f.write_str("Allocation ratios must not all be zero"),
+            Self::InvalidAmount(s) => write!(f, "Invalid amount `{}`", s),
+            Self::UnknownCurrency(s) => write!(f, "Unknown currency `{}`", s),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+fn minor_units_per_major(currency: Currency) -> Result<i128, MoneyError> {
+    10i128
+        .checked_pow(currency.minor_units as u32)
+        .ok_or(MoneyError::Overflow)
+}
+
+/// Divides `numerator` by `denominator`, rounding the quotient according to the given mode.
+fn div_round(
+    numerator: i128,
+    denominator: i128,
+    rounding: RoundingMode,
+) -> Result<i128, MoneyError> {
+    if denominator == 0 {
+        return Err(MoneyError::DivisionByZero);
+    }
+
+    let (numerator, denominator) = if denominator < 0 {
+        (
+            numerator.checked_neg().ok_or(MoneyError::Overflow)?,
+            denominator.checked_neg().ok_or(MoneyError::Overflow)?,
+        )
+    } else {
+        (numerator, denominator)
+    };
+
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+
+    if remainder == 0 {
+        return Ok(quotient);
+    }
+
+    let sign = numerator.signum();
+    let half = (remainder.unsigned_abs() * 2).cmp(&denominator.unsigned_abs());
+
+    let away_from_zero = match rounding {
+        RoundingMode::Up => true,
+        RoundingMode::Down => false,
+        RoundingMode::Ceiling => sign > 0,
+        RoundingMode::Floor => sign < 0,
+        RoundingMode::HalfUp => half != Ordering::Less,
+        RoundingMode::HalfDown => half == Ordering::Greater,
+        RoundingMode::HalfEven => {
+            half == Ordering::Greater || half == Ordering::Equal && quotient % 2 != 0
+        }
+    };
+
+    Ok(if away_from_zero {
+        quotient + sign
+    } else {
+        quotient
+    })
+}
@@ -0,0 +1,167 @@
+use std::marker::PhantomData;
+
+use crate::application::{ReadRepository, RepositoryError};
+
+use super::{AggregateRoot, Entity};
+
+/// A typed reference to an [AggregateRoot], by its identity.
+///
+/// > Choose one Entity to be the root of each Aggregate, and control all access to the objects
+/// > inside the boundary through the root. Allow external objects to hold references to the root
+/// > only.
+///
+/// Aggregates should reference other aggregates only by identity, so that each one remains its own
+/// consistency boundary. Unlike a bare identity, an [AggregateRef] cannot be mistaken for a reference
+/// to an aggregate of another type, even when both share the same identity type.
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::{
+///     application::Repository,
+///     domain::{AggregateRef, Entity},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Customer {
+///     #[entity(id)]
+///     id: u32,
+///     name: String,
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Order {
+///     #[entity(id)]
+///     id: u32,
+///     customer: AggregateRef<Customer>,
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let customers = InMemoryRepository::new();
+///
+/// let customer = customers
+///     .add(Customer { id: 42, name: "John Doe".to_string() })
+///     .await
+///     .unwrap();
+///
+/// let order = Order { id: 1, customer: AggregateRef::from(&customer) };
+///
+/// assert_eq!(*order.customer.id(), 42);
+///
+/// // Resolve the reference through the referenced aggregate's repository.
+/// let customer = order.customer.resolve(&customers).await.unwrap().unwrap();
+///
+/// assert_eq!(customer.name, "John Doe");
+/// # })
+/// ```
+///
+/// Deriving [AggregateRoot] fails for aggregates that embed another aggregate root, which should
+/// be referenced by identity instead:
+///
+/// ```compile_fail
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity)]
+/// struct Customer {
+///     #[entity(id)]
+///     id: u32,
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity)]
+/// struct Order {
+///     #[entity(id)]
+///     id: u32,
+///     customer: Customer,
+/// }
+/// ```
+pub struct AggregateRef<T: AggregateRoot> {
+    id: <T as Entity>::Id,
+    _aggregate: PhantomData<fn() -> T>,
+}
+
+impl<T: AggregateRoot> AggregateRef<T> {
+    /// Creates a new reference to the aggregate with the given identity.
+    pub fn new(id: <T as Entity>::Id) -> Self {
+        Self {
+            id,
+            _aggregate: PhantomData,
+        }
+    }
+
+    /// Identity of the referenced aggregate.
+    pub fn id(&self) -> &<T as Entity>::Id {
+        &self.id
+    }
+
+    /// Converts the reference into the identity of the referenced aggregate.
+    pub fn into_id(self) -> <T as Entity>::Id {
+        self.id
+    }
+
+    /// Gets the referenced aggregate from the given repository.
+    pub async fn resolve(
+        &self,
+        repository: &dyn ReadRepository<T>,
+    ) -> crate::Result<Option<T>, RepositoryError> {
+        repository.get_by_id(self.id.clone()).await
+    }
+}
+
+impl<T: AggregateRoot> From<&T> for AggregateRef<T> {
+    fn from(aggregate: &T) -> Self {
+        Self::new(aggregate.id().clone())
+    }
+}
+
+impl<T: AggregateRoot> Clone for AggregateRef<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.id.clone())
+    }
+}
+
+impl<T: AggregateRoot> Copy for AggregateRef<T> where <T as Entity>::Id: Copy {}
+
+impl<T: AggregateRoot> PartialEq for AggregateRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T: AggregateRoot> Eq for AggregateRef<T> where <T as Entity>::Id: Eq {}
+
+impl<T: AggregateRoot> std::hash::Hash for AggregateRef<T>
+where
+    <T as Entity>::Id: std::hash::Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T: AggregateRoot> std::fmt::Debug for AggregateRef<T>
+where
+    <T as Entity>::Id: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AggregateRef").field(&self.id).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: AggregateRoot> serde::Serialize for AggregateRef<T>
+where
+    <T as Entity>::Id: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.id.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: AggregateRoot> serde::Deserialize<'de> for AggregateRef<T>
+where
+    <T as Entity>::Id: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <T as Entity>::Id::deserialize(deserializer).map(Self::new)
+    }
+}
@@ -0,0 +1,190 @@
+/// Trait for representing a **Domain Service**.
+///
+/// > When a significant process or transformation in the domain is not a natural responsibility of
+/// > an Entity or Value Object, add an operation to the model as a standalone interface declared as
+/// > a Service. Make the Service stateless.
+///
+/// Domain services hold business logic that spans several aggregates, such as pricing or transfers
+/// between accounts. They operate on aggregates given to them, and never access repositories, which
+/// are the responsibility of the Application layer.
+///
+/// # Examples
+///
+/// Implement this trait explicitly, or use closures with the `Fn(Args) -> Output` signature. Domain
+/// services may also be passed to aggregate methods that depend on them:
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use ddd_rs::{
+///     application::{DomainEventHandler, ReadRepository, Repository, RepositoryEx},
+///     domain::{AggregateRootEx, DomainEventEnvelope, DomainService},
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum AccountEvent {
+///     Deposited(u64),
+///     Withdrawn(u64),
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Account {
+///     #[entity(id)]
+///     id: u32,
+///     balance: u64,
+///     #[aggregate_root(domain_events)]
+///     domain_events: Vec<AccountEvent>,
+/// }
+///
+/// impl Account {
+///     fn deposit(&mut self, amount: u64) {
+///         self.balance += amount;
+///         self.register_domain_event(AccountEvent::Deposited(amount));
+///     }
+///
+///     // The fee policy is a domain service, given to the aggregate by its caller.
+///     fn withdraw(
+///         &mut self,
+///         amount: u64,
+///         fees: &impl DomainService<u64, Output = u64>,
+///     ) -> Result<(), &'static str> {
+///         let amount = amount + fees.execute(amount);
+///
+///         if amount > self.balance {
+///             return Err("Insufficient funds");
+///         }
+///
+///         self.balance -= amount;
+///         self.register_domain_event(AccountEvent::Withdrawn(amount));
+///
+///         Ok(())
+///     }
+/// }
+///
+/// // Transfers span two aggregates, hence they are a natural responsibility of neither.
+/// struct FundsTransfer<F> {
+///     fees: F,
+/// }
+///
+/// impl<'a, F: DomainService<u64, Output = u64>> DomainService<(&'a mut Account, &'a mut Account, u64)>
+///     for FundsTransfer<F>
+/// {
+///     type Output = Result<(), &'static str>;
+///
+///     fn execute(&self, (from, to, amount): (&mut Account, &mut Account, u64)) -> Self::Output {
+///         from.withdraw(amount, &self.fees)?;
+///         to.deposit(amount);
+///
+///         Ok(())
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Ledger {
+///     entries: Mutex<Vec<(u32, AccountEvent)>>,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl DomainEventHandler<Account> for Ledger {
+///     async fn handle(
+///         &self,
+///         entity: Account,
+///         envelope: DomainEventEnvelope<AccountEvent, u32>,
+///     ) -> ddd_rs::Result<Account> {
+///         self.entries.lock().unwrap().push((envelope.aggregate_id, envelope.event));
+///
+///         Ok(entity)
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let ledger = Arc::new(Ledger::default());
+/// let repository = RepositoryEx::new(ledger.clone(), Arc::new(InMemoryRepository::new()));
+///
+/// repository.add(Account { id: 1, balance: 1000, domain_events: vec![] }).await.unwrap();
+/// repository.add(Account { id: 2, balance: 0, domain_events: vec![] }).await.unwrap();
+///
+/// // The Application layer loads the aggregates, delegates to the domain service, then persists
+/// // them.
+/// let transfer = FundsTransfer { fees: |amount: u64| amount / 100 };
+///
+/// let mut from = repository.get_by_id(1).await.unwrap().unwrap();
+/// let mut to = repository.get_by_id(2).await.unwrap().unwrap();
+///
+/// transfer.execute((&mut from, &mut to, 500)).unwrap();
+///
+/// assert_eq!(transfer.execute((&mut from, &mut to, 500)), Err("Insufficient funds"));
+///
+/// repository.update(from).await.unwrap();
+/// repository.update(to).await.unwrap();
+///
+/// assert_eq!(repository.get_by_id(1).await.unwrap().unwrap().balance, 495);
+/// assert_eq!(repository.get_by_id(2).await.unwrap().unwrap().balance, 500);
+///
+/// assert_eq!(
+///     *ledger.entries.lock().unwrap(),
+///     [(1, AccountEvent::Withdrawn(505)), (2, AccountEvent::Deposited(500))]
+/// );
+/// # })
+/// ```
+pub trait DomainService<Args>: Send + Sync {
+    /// Domain service output type.
+    type Output;
+
+    /// Executes the domain service.
+    fn execute(&self, args: Args) -> Self::Output;
+}
+
+impl<Args, Output, F: Fn(Args) -> Output + Send + Sync> DomainService<Args> for F {
+    type Output = Output;
+
+    fn execute(&self, args: Args) -> Self::Output {
+        self(args)
+    }
+}
+
+/// Trait for representing an asynchronous [DomainService].
+///
+/// Asynchronous domain services are meant for domain logic that depends on external information,
+/// such as exchange rates, which should be awaited by the Application layer before handing its
+/// result to the aggregates.
+///
+/// # Examples
+///
+/// ```
+/// use ddd_rs::domain::{AsyncDomainService, DomainService};
+///
+/// struct ExchangeRates;
+///
+/// #[async_trait::async_trait]
+/// impl AsyncDomainService<(&'static str, &'static str)> for ExchangeRates {
+///     type Output = ddd_rs::Result<f64>;
+///
+///     async fn execute(&self, (from, to): (&'static str, &'static str)) -> Self::Output {
+///         // Fetch the rate from an external provider...
+///         match (from, to) {
+///             ("USD", "EUR") => Ok(0.5),
+///             _ => Err(format!("Unknown rate from {from} to {to}").into()),
+///         }
+///     }
+/// }
+///
+/// // Sync domain services can then be built from the awaited information.
+/// let convert = |rate: f64| move |amount: u64| (amount as f64 * rate) as u64;
+///
+/// # tokio_test::block_on(async {
+/// let rate = ExchangeRates.execute(("USD", "EUR")).await.unwrap();
+///
+/// assert_eq!(convert(rate).execute(100), 50);
+/// assert!(ExchangeRates.execute(("USD", "BRL")).await.is_err());
+/// # })
+/// ```
+#[async_trait::async_trait]
+pub trait AsyncDomainService<Args: Send>: Send + Sync {
+    /// Domain service output type.
+    type Output: Send;
+
+    /// Executes the domain service.
+    async fn execute(&self, args: Args) -> Self::Output;
+}
@@ -0,0 +1,76 @@
+use std::time::SystemTime;
+
+/// Trait for representing a **Soft-Deletable** [AggregateRoot](super::AggregateRoot).
+///
+/// Deleting such an aggregate only marks it as deleted, so that it can later be restored.
+/// Repositories hide deleted aggregates by default, and implement
+/// [SoftDeleteRepository](crate::application::SoftDeleteRepository) in order to restore or purge
+/// them.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::AggregateRoot](crate::AggregateRoot) macro, by
+/// tagging an `Option<SystemTime>` field with the `#[aggregate_root(soft_delete)]` attribute:
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// use ddd_rs::{
+///     application::{FixedClock, ReadRepository, Repository, RepositoryError, SoftDeleteRepository},
+///     domain::SoftDeletable,
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone)]
+/// struct Customer {
+///     #[entity(id)]
+///     id: u32,
+///     #[aggregate_root(soft_delete)]
+///     deleted_at: Option<SystemTime>,
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let now = SystemTime::UNIX_EPOCH + Duration::from_secs(60);
+///
+/// let repository = InMemoryRepository::new().with_clock(std::sync::Arc::new(FixedClock::new(now)));
+///
+/// let customer = repository.add(Customer { id: 1, deleted_at: None }).await.unwrap();
+///
+/// repository.add(Customer { id: 2, deleted_at: None }).await.unwrap();
+///
+/// // Deleted aggregates are hidden, but kept.
+/// repository.delete(customer).await.unwrap();
+///
+/// assert!(repository.get_by_id(1).await.unwrap().is_none());
+/// assert_eq!(repository.count().await.unwrap(), 1);
+///
+/// let all = repository.list_including_deleted(0, 10).await.unwrap();
+///
+/// assert_eq!(all.len(), 2);
+/// assert_eq!(all.iter().find(|c| c.id == 1).unwrap().deleted_at(), Some(now));
+///
+/// // Deleted aggregates may be restored...
+/// let customer = repository.restore(1).await.unwrap();
+///
+/// assert!(!customer.is_deleted());
+/// assert!(repository.exists(1).await.unwrap());
+///
+/// // ...or purged for good.
+/// repository.purge(1).await.unwrap();
+///
+/// assert!(matches!(repository.restore(1).await, Err(RepositoryError::NotFound)));
+/// assert_eq!(repository.list_including_deleted(0, 10).await.unwrap().len(), 1);
+/// # })
+/// ```
+pub trait SoftDeletable {
+    /// Instant the aggregate was deleted at, if it was.
+    fn deleted_at(&self) -> Option<SystemTime>;
+
+    /// Sets or clears the instant the aggregate was deleted at.
+    fn set_deleted_at(&mut self, deleted_at: Option<SystemTime>);
+
+    /// Checks whether the aggregate was deleted.
+    fn is_deleted(&self) -> bool {
+        self.deleted_at().is_some()
+    }
+}
@@ -0,0 +1,139 @@
+use super::AggregateRoot;
+
+/// Trait for representing a **Specification**.
+///
+/// > Create explicit predicate-like Value Objects for specialized purposes. A Specification is a
+/// > predicate that determines if an object does or does not satisfy some criteria.
+///
+/// Specifications can be combined with the [and](Specification::and), [or](Specification::or) and
+/// [not](Specification::not) combinators, and are used for querying repositories through
+/// [ReadRepository::find](crate::application::ReadRepository::find) and its siblings.
+///
+/// # Examples
+///
+/// Implement this trait explicitly, or use closures with the `Fn(&T) -> bool` signature:
+///
+/// ```
+/// use ddd_rs::{
+///     application::{ReadRepository, Repository},
+///     domain::Specification,
+///     infrastructure::InMemoryRepository,
+/// };
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity, Clone, Debug)]
+/// struct Order {
+///     #[entity(id)]
+///     id: u32,
+///     customer_id: u32,
+///     total: u32,
+/// }
+///
+/// struct PlacedBy(u32);
+///
+/// impl Specification<Order> for PlacedBy {
+///     fn is_satisfied_by(&self, order: &Order) -> bool {
+///         order.customer_id == self.0
+///     }
+/// }
+///
+/// let expensive = |order: &Order| order.total >= 100;
+///
+/// let order = Order { id: 1, customer_id: 42, total: 150 };
+///
+/// assert!(PlacedBy(42).is_satisfied_by(&order));
+/// assert!(PlacedBy(42).and(expensive).is_satisfied_by(&order));
+/// assert!(PlacedBy(7).or(expensive).is_satisfied_by(&order));
+/// assert!(!PlacedBy(42).and(expensive.not()).is_satisfied_by(&order));
+///
+/// # tokio_test::block_on(async {
+/// let repository: InMemoryRepository<Order> = InMemoryRepository::new();
+///
+/// repository.add(Order { id: 1, customer_id: 42, total: 150 }).await.unwrap();
+/// repository.add(Order { id: 2, customer_id: 42, total: 50 }).await.unwrap();
+/// repository.add(Order { id: 3, customer_id: 7, total: 200 }).await.unwrap();
+///
+/// // Query the repository through specifications.
+/// let mut orders = repository.find(&PlacedBy(42)).await.unwrap();
+///
+/// orders.sort_by_key(|o| o.id);
+///
+/// assert_eq!(orders.iter().map(|o| o.id).collect::<Vec<_>>(), [1, 2]);
+///
+/// let order = repository.find_one(&PlacedBy(7)).await.unwrap();
+///
+/// assert_eq!(order.map(|o| o.id), Some(3));
+///
+/// assert_eq!(repository.count_by(&expensive).await.unwrap(), 2);
+/// assert!(!repository.exists_by(&PlacedBy(1)).await.unwrap());
+/// # })
+/// ```
+pub trait Specification<T: AggregateRoot>: Send + Sync {
+    /// Checks whether the candidate satisfies the specification.
+    fn is_satisfied_by(&self, candidate: &T) -> bool;
+
+    /// Combines this specification with another one, requiring both to be satisfied.
+    fn and<S: Specification<T>>(self, other: S) -> And<Self, S>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Combines this specification with another one, requiring either of them to be satisfied.
+    fn or<S: Specification<T>>(self, other: S) -> Or<Self, S>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// Negates this specification.
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+impl<T: AggregateRoot, F: Fn(&T) -> bool + Send + Sync> Specification<T> for F {
+    fn is_satisfied_by(&self, candidate: &T) -> bool {
+        self(candidate)
+    }
+}
+
+/// [Specification] satisfied when both of its inner specifications are.
+///
+/// See [Specification::and].
+#[derive(Debug, Clone, Copy)]
+pub struct And<A, B>(A, B);
+
+impl<T: AggregateRoot, A: Specification<T>, B: Specification<T>> Specification<T> for And<A, B> {
+    fn is_satisfied_by(&self, candidate: &T) -> bool {
+        self.0.is_satisfied_by(candidate) && self.1.is_satisfied_by(candidate)
+    }
+}
+
+/// [Specification] satisfied when either of its inner specifications is.
+///
+/// See [Specification::or].
+#[derive(Debug, Clone, Copy)]
+pub struct Or<A, B>(A, B);
+
+impl<T: AggregateRoot, A: Specification<T>, B: Specification<T>> Specification<T> for Or<A, B> {
+    fn is_satisfied_by(&self, candidate: &T) -> bool {
+        self.0.is_satisfied_by(candidate) || self.1.is_satisfied_by(candidate)
+    }
+}
+
+/// [Specification] satisfied when its inner specification is not.
+///
+/// See [Specification::not].
+#[derive(Debug, Clone, Copy)]
+pub struct Not<S>(S);
+
+impl<T: AggregateRoot, S: Specification<T>> Specification<T> for Not<S> {
+    fn is_satisfied_by(&self, candidate: &T) -> bool {
+        !self.0.is_satisfied_by(candidate)
+    }
+}
@@ -0,0 +1,151 @@
+/// Trait for representing a **State Machine**, usually the lifecycle status of an
+/// [AggregateRoot](super::AggregateRoot).
+///
+/// The allowed transitions are declared in a single place, rather than being scattered across the
+/// aggregate's methods, and illegal ones are rejected with an [IllegalTransition] error.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::StateMachine](crate::StateMachine) macro, listing
+/// the states each variant may transition to with the `#[state_machine(to(...))]` attribute:
+///
+/// ```
+/// use ddd_rs::domain::{IllegalTransition, StateMachine};
+///
+/// #[derive(ddd_rs::StateMachine, Clone, Copy, Debug, PartialEq)]
+/// enum DocumentStatus {
+///     #[state_machine(to(Submitted))]
+///     Draft,
+///     #[state_machine(to(Approved, Draft))]
+///     Submitted,
+///     #[state_machine(to(Archived))]
+///     Approved,
+///     Archived,
+/// }
+///
+/// let mut status = DocumentStatus::Draft;
+///
+/// assert!(status.can_transition_to(&DocumentStatus::Submitted));
+/// assert!(!status.can_transition_to(&DocumentStatus::Approved));
+///
+/// // The previous state is returned upon each transition.
+/// assert_eq!(status.transition_to(DocumentStatus::Submitted), Ok(DocumentStatus::Draft));
+/// assert_eq!(status.transition_to(DocumentStatus::Approved), Ok(DocumentStatus::Submitted));
+///
+/// assert_eq!(
+///     status.transition_to(DocumentStatus::Draft),
+///     Err(IllegalTransition {
+///         from: DocumentStatus::Approved,
+///         to: DocumentStatus::Draft
+///     })
+/// );
+///
+/// // Final states cannot transition to any other state.
+/// status.transition_to(DocumentStatus::Archived).unwrap();
+///
+/// assert!(status.transition_to(DocumentStatus::Archived).is_err());
+/// ```
+///
+/// Aggregate roots may tag their status field with the `#[aggregate_root(state)]` attribute, which
+/// derives a `transition_to` method for it. Use the `transition_event = path::to::fn` attribute
+/// along with it to register the `fn(&Self, &From, &To) -> Self::DomainEvent` domain event upon
+/// each transition:
+///
+/// ```
+/// use ddd_rs::domain::{AggregateRootEx, IllegalTransition};
+///
+/// #[derive(ddd_rs::StateMachine, Clone, Copy, Debug, PartialEq)]
+/// enum DocumentStatus {
+///     #[state_machine(to(Submitted))]
+///     Draft,
+///     #[state_machine(to(Approved, Draft))]
+///     Submitted,
+///     Approved,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum DocumentEvent {
+///     StatusChanged {
+///         id: u32,
+///         from: DocumentStatus,
+///         to: DocumentStatus,
+///     },
+/// }
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity)]
+/// struct Document {
+///     #[entity(id)]
+///     id: u32,
+///     #[aggregate_root(state, transition_event = status_changed)]
+///     status: DocumentStatus,
+///     #[aggregate_root(domain_events)]
+///     domain_events: Vec<DocumentEvent>,
+/// }
+///
+/// fn status_changed(document: &Document, from: &DocumentStatus, to: &DocumentStatus) -> DocumentEvent {
+///     DocumentEvent::StatusChanged {
+///         id: document.id,
+///         from: *from,
+///         to: *to,
+///     }
+/// }
+///
+/// let mut document = Document {
+///     id: 1,
+///     status: DocumentStatus::Draft,
+///     domain_events: vec![],
+/// };
+///
+/// document.transition_to(DocumentStatus::Submitted).unwrap();
+///
+/// // Illegal transitions leave the aggregate untouched.
+/// assert!(document.transition_to(DocumentStatus::Submitted).is_err());
+/// assert_eq!(document.status, DocumentStatus::Submitted);
+///
+/// assert_eq!(
+///     document.take_domain_events(),
+///     [DocumentEvent::StatusChanged {
+///         id: 1,
+///         from: DocumentStatus::Draft,
+///         to: DocumentStatus::Submitted
+///     }]
+/// );
+/// ```
+pub trait StateMachine: Clone {
+    /// Checks whether the transition to the given state is allowed.
+    fn can_transition_to(&self, next: &Self) -> bool;
+
+    /// Transitions to the given state, returning the previous one, or fails if the transition is
+    /// not allowed.
+    fn transition_to(&mut self, next: Self) -> Result<Self, IllegalTransition<Self>> {
+        if !self.can_transition_to(&next) {
+            return Err(IllegalTransition {
+                from: self.clone(),
+                to: next,
+            });
+        }
+
+        Ok(std::mem::replace(self, next))
+    }
+}
+
+/// Error returned when a [StateMachine] is not allowed to transition between two states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IllegalTransition<S> {
+    /// State the transition was attempted from.
+    pub from: S,
+    /// State the transition was attempted to.
+    pub to: S,
+}
+
+impl<S: std::fmt::Debug> std::fmt::Display for IllegalTransition<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Illegal transition from {:?} to {:?}",
+            self.from, self.to
+        )
+    }
+}
+
+impl<S: std::fmt::Debug> std::error::Error for IllegalTransition<S> {}
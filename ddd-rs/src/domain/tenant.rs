@@ -0,0 +1,38 @@
+use super::AggregateRoot;
+
+/// Trait for representing a **Tenant-Scoped** [AggregateRoot], which belongs to a single tenant.
+///
+/// Wrap repositories of such aggregates in a
+/// [TenantRepository](crate::application::TenantRepository), so that every read and write is
+/// scoped to the current tenant.
+///
+/// # Examples
+///
+/// Derive its implementation using the [ddd_rs::AggregateRoot](crate::AggregateRoot) macro, by
+/// tagging the tenant identity field with the `#[aggregate_root(tenant)]` attribute:
+///
+/// ```
+/// use ddd_rs::domain::TenantScoped;
+///
+/// #[derive(ddd_rs::AggregateRoot, ddd_rs::Entity)]
+/// struct Invoice {
+///     #[entity(id)]
+///     id: u32,
+///     #[aggregate_root(tenant)]
+///     tenant_id: String,
+/// }
+///
+/// let invoice = Invoice {
+///     id: 1,
+///     tenant_id: "acme".to_string(),
+/// };
+///
+/// assert_eq!(invoice.tenant_id(), "acme");
+/// ```
+pub trait TenantScoped: AggregateRoot {
+    /// Tenant identity type.
+    type TenantId: Clone + PartialEq + Send + Sync + 'static;
+
+    /// Identity of the tenant the aggregate belongs to.
+    fn tenant_id(&self) -> &Self::TenantId;
+}
@@ -34,4 +34,200 @@
 /// assert_eq!(a.y, a_clone.y);
 /// assert_eq!(a.z, a_clone.z);
 /// ```
+///
+/// Tuple structs, unit structs and enums are also supported, in which case every field is an
+/// equality component when none of them is tagged (unlike named structs, which then have none).
+/// Newtypes also get [Deref](std::ops::Deref), [AsRef] and [From] implementations for their inner
+/// value:
+///
+/// ```
+/// #[derive(ddd_rs::ValueObject, Debug)]
+/// struct Email(String);
+///
+/// #[derive(ddd_rs::ValueObject, Debug)]
+/// enum Currency {
+///     Brl,
+///     Usd,
+///     Other { code: String },
+/// }
+///
+/// let email = Email::from("john.doe@example.com".to_string());
+///
+/// assert_eq!(email, Email::from("john.doe@example.com".to_string()));
+/// assert!(email.ends_with("@example.com"));
+///
+/// assert_eq!(Currency::Brl, Currency::Brl.clone());
+/// assert_ne!(Currency::Brl, Currency::Usd);
+/// assert_ne!(
+///     Currency::Other { code: "EUR".to_string() },
+///     Currency::Other { code: "JPY".to_string() }
+/// );
+/// ```
+///
+/// Use the `#[value_object(hash, ord)]` attribute to also derive [Eq], [Hash](std::hash::Hash),
+/// [PartialOrd] and [Ord] from the same equality components, and `#[value_object(copy)]` to derive
+/// [Copy], bounded on every field being `Copy`:
+///
+/// ```
+/// use std::collections::{BTreeSet, HashMap};
+///
+/// #[derive(ddd_rs::ValueObject, Debug)]
+/// #[value_object(hash, ord)]
+/// struct Sku {
+///     #[value_object(eq)]
+///     code: String,
+///     description: String,
+/// }
+///
+/// #[derive(ddd_rs::ValueObject, Debug)]
+/// #[value_object(hash, ord, copy)]
+/// enum Size {
+///     Small,
+///     Medium,
+///     Custom(u32),
+/// }
+///
+/// let sku = |code: &str, description: &str| Sku {
+///     code: code.to_string(),
+///     description: description.to_string(),
+/// };
+///
+/// // Values that are equal also hash equally, hence they are the same key.
+/// let mut stock = HashMap::new();
+///
+/// stock.insert(sku("A-1", "T-shirt"), 10);
+/// stock.insert(sku("A-1", "Blue T-shirt"), 20);
+///
+/// assert_eq!(stock.len(), 1);
+/// assert_eq!(stock[&sku("A-1", "")], 20);
+///
+/// // Enum values are ordered by variant first, then by their equality components.
+/// let sizes = BTreeSet::from([Size::Custom(42), Size::Medium, Size::Custom(7), Size::Small]);
+///
+/// assert_eq!(
+///     sizes.into_iter().collect::<Vec<_>>(),
+///     [Size::Small, Size::Medium, Size::Custom(7), Size::Custom(42)]
+/// );
+///
+/// let size = Size::Custom(42);
+/// let copy = size;
+///
+/// assert_eq!(size, copy);
+/// ```
 pub trait ValueObject: Clone + PartialEq {}
+
+/// Error returned when constructing an invalid [ValueObject], listing every violated rule.
+///
+/// # Examples
+///
+/// Derive a fallible `try_new` constructor and a `TryFrom` implementation using the
+/// [ddd_rs::ValueObject](crate::ValueObject) macro, along with:
+///
+/// - `#[value_object(validate = path::to::fn)]`: A custom validation function, with signature
+///   `fn(&Self) -> Result<(), ValidationError>`;
+/// - `#[value_object(trim, lowercase, uppercase)]`: Normalization of string fields, applied before
+///   validation;
+/// - `#[value_object(non_empty, min_len = N, max_len = N)]`: Length constraints on fields that have
+///   `is_empty` and `len` methods. Lengths of `String` and `str` fields are counted in characters,
+///   rather than bytes;
+/// - `#[value_object(min = expr, max = expr)]`: Range constraints on fields that implement
+///   [PartialOrd].
+///
+/// ```
+/// use ddd_rs::domain::{ValidationError, Violation};
+///
+/// #[derive(ddd_rs::ValueObject, Debug)]
+/// #[value_object(validate = Email::validate)]
+/// struct Email {
+///     #[value_object(eq, trim, lowercase, non_empty, max_len = 254)]
+///     address: String,
+/// }
+///
+/// impl Email {
+///     fn validate(&self) -> Result<(), ValidationError> {
+///         if !self.address.contains('@') {
+///             return Err(Violation::new("format", "must contain an `@`").into());
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// #[derive(ddd_rs::ValueObject, Debug)]
+/// struct Quantity {
+///     #[value_object(eq, min = 1, max = 100)]
+///     value: i32,
+/// }
+///
+/// // Fields are normalized prior to being validated.
+/// let email = Email::try_new("  John.Doe@Example.com ".to_string()).unwrap();
+///
+/// assert_eq!(email.address, "john.doe@example.com");
+///
+/// // Every violated rule is reported.
+/// let error = Email::try_new("   ".to_string()).unwrap_err();
+///
+/// assert_eq!(
+///     error.violations(),
+///     [
+///         Violation::for_field("address", "non_empty", "must not be empty"),
+///         Violation::new("format", "must contain an `@`"),
+///     ]
+/// );
+///
+/// // Single-field value objects may also be converted from their field's value.
+/// let quantity = Quantity::try_from(42).unwrap();
+///
+/// assert_eq!(quantity.value, 42);
+///
+/// let error = Quantity::try_from(-1).unwrap_err();
+///
+/// assert_eq!(error.to_string(), "`value` must be at least 1");
+///
+/// // String lengths are counted in characters.
+/// assert!(Email::try_new(format!("{}@b.c", "é".repeat(250))).is_ok());
+/// assert!(Email::try_new(format!("{}@b.c", "é".repeat(251))).is_err());
+/// ```
+pub type ValidationError = super::Violations<Violation>;
+
+/// A single rule violated when constructing a [ValueObject].
+///
+/// See [ValidationError] for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Field that violated the rule, if any.
+    pub field: Option<&'static str>,
+    /// Name of the violated rule.
+    pub rule: &'static str,
+    /// Description of the violation.
+    pub message: String,
+}
+
+impl Violation {
+    /// Creates a new [Violation] of a rule that applies to the value object as a whole.
+    pub fn new(rule: &'static str, message: impl ToString) -> Self {
+        Self {
+            field: None,
+            rule,
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates a new [Violation] of a rule that applies to a single field of the value object.
+    pub fn for_field(field: &'static str, rule: &'static str, message: impl ToString) -> Self {
+        Self {
+            field: Some(field),
+            rule,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.field {
+            Some(field) => write!(f, "`{}` {}", field, self.message),
+            None => self.message.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+/// List of the rules violated by a domain object, in order of evaluation.
+///
+/// This is the error type shared by [ValidationError](super::ValidationError) and
+/// [InvariantError](super::InvariantError), whose examples show its usage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violations<V> {
+    violations: Vec<V>,
+}
+
+impl<V> Violations<V> {
+    /// Creates a new [Violations] from the given violations.
+    pub fn new(violations: Vec<V>) -> Self {
+        Self { violations }
+    }
+
+    /// Violated rules, in order of evaluation.
+    pub fn violations(&self) -> &[V] {
+        &self.violations
+    }
+}
+
+impl<V> Default for Violations<V> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<V> From<V> for Violations<V> {
+    fn from(violation: V) -> Self {
+        Self::new(vec![violation])
+    }
+}
+
+impl<V> FromIterator<V> for Violations<V> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<V> IntoIterator for Violations<V> {
+    type Item = V;
+    type IntoIter = std::vec::IntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.violations.into_iter()
+    }
+}
+
+impl<V: std::fmt::Display> std::fmt::Display for Violations<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+
+            violation.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<V: std::fmt::Debug + std::fmt::Display> std::error::Error for Violations<V> {}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::application::{ConcurrencyError, EventStore, SerializedEvent};
+
+/// An in-memory implementation of [EventStore], using a [HashMap] of streams.
+///
+/// See the example on [EventStore] for usage information of this event store implementation.
+pub struct InMemoryEventStore<P> {
+    streams: std::sync::RwLock<HashMap<String, Vec<SerializedEvent<P>>>>,
+}
+
+impl<P> InMemoryEventStore<P> {
+    /// Creates a new [InMemoryEventStore].
+    pub fn new() -> Self {
+        Self {
+            streams: std::sync::RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<P> Default for InMemoryEventStore<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: Clone + Send + Sync> EventStore<P> for InMemoryEventStore<P> {
+    async fn append(
+        &self,
+        stream_id: &str,
+        expected_version: u64,
+        events: Vec<SerializedEvent<P>>,
+    ) -> crate::Result<u64> {
+        let mut wo_streams = self.streams.write().unwrap();
+
+        let stream = wo_streams.entry(stream_id.to_string()).or_default();
+        let actual = stream.len() as u64;
+
+        if actual != expected_version {
+            return Err(ConcurrencyError {
+                expected: expected_version,
+                actual,
+            }
+            .into());
+        }
+
+        stream.extend(events);
+
+        Ok(stream.len() as u64)
+    }
+
+    async fn load(
+        &self,
+        stream_id: &str,
+        from_version: u64,
+    ) -> crate::Result<Vec<SerializedEvent<P>>> {
+        let ro_streams = self.streams.read().unwrap();
+
+        let events = ro_streams
+            .get(stream_id)
+            .map(|stream| stream.iter().skip(from_version as usize).cloned().collect())
+            .unwrap_or_default();
+
+        Ok(events)
+    }
+}
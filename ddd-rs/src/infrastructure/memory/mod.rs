@@ -1,2 +1,11 @@
+mod event_store;
+pub use event_store::*;
+
 mod repository;
 pub use repository::*;
+
+mod saga_store;
+pub use saga_store::*;
+
+mod snapshot_store;
+pub use snapshot_store::*;
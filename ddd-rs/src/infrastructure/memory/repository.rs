@@ -1,22 +1,54 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use crate::application::{ReadRepository, Repository};
-use crate::domain::{AggregateRoot, Entity};
+use crate::application::{
+    Clock, ConcurrencyError, ReadRepository, Repository, RepositoryError, SoftDeleteRepository,
+    SystemClock,
+};
+use crate::domain::{AggregateRoot, Entity, Specification};
 
 /// An in-memory implementation of [Repository], using a [HashMap].
 ///
 /// See the example on [Repository] for usage information of this repository implementation.
+///
+/// Adding an existing aggregate fails with [RepositoryError::AlreadyExists], while updating or
+/// deleting a missing one fails with [RepositoryError::NotFound].
+///
+/// Aggregates have their [Invariants](crate::domain::Invariants) checked before each write, and
+/// [Versioned](crate::domain::Versioned) aggregates have their version checked against the stored
+/// one upon updates, which is then advanced by their number of
+/// [pending changes](AggregateRoot::pending_changes).
+/// [ChangeTracked](crate::domain::ChangeTracked) aggregates have their changes cleared once
+/// persisted.
+///
+/// Aggregates are stored without their pending domain events, which are left on the returned
+/// aggregate to be taken by the caller (e.g. a [RepositoryEx](crate::application::RepositoryEx)).
+///
+/// [SoftDeletable](crate::domain::SoftDeletable) aggregates are only marked as deleted, and hidden
+/// from every read operation other than
+/// [list_including_deleted](SoftDeleteRepository::list_including_deleted). The stored aggregate is
+/// marked, hence any unsaved modifications of the deleted one are discarded.
 pub struct InMemoryRepository<T: AggregateRoot> {
     entities: std::sync::RwLock<HashMap<<T as Entity>::Id, T>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl<T: AggregateRoot> InMemoryRepository<T> {
     /// Creates a new [InMemoryRepository].
+    ///
+    /// Soft-deleted aggregates are timestamped by the [SystemClock].
     pub fn new() -> Self {
         Self {
             entities: std::sync::RwLock::new(HashMap::new()),
+            clock: Arc::new(SystemClock),
         }
     }
+
+    /// Sets the [Clock] used for timestamping soft-deleted aggregates.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
 }
 
 impl<T: AggregateRoot> Default for InMemoryRepository<T> {
@@ -30,19 +62,20 @@ impl<T: AggregateRoot + Clone> ReadRepository<T> for InMemoryRepository<T>
 where
     <T as Entity>::Id: std::hash::Hash + Eq,
 {
-    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>> {
+    async fn get_by_id(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>, RepositoryError> {
         let ro_entities = self.entities.read().unwrap();
 
-        let entity = ro_entities.get(&id).cloned();
+        let entity = ro_entities.get(&id).filter(|e| !is_deleted(*e)).cloned();
 
         Ok(entity)
     }
 
-    async fn list(&self, skip: usize, take: usize) -> crate::Result<Vec<T>> {
+    async fn list(&self, skip: usize, take: usize) -> crate::Result<Vec<T>, RepositoryError> {
         let ro_entities = self.entities.read().unwrap();
 
         let entities = ro_entities
             .values()
+            .filter(|e| !is_deleted(*e))
             .skip(skip)
             .take(take)
             .cloned()
@@ -51,10 +84,55 @@ where
         Ok(entities)
     }
 
-    async fn count(&self) -> crate::Result<usize> {
+    async fn count(&self) -> crate::Result<usize, RepositoryError> {
+        let ro_entities = self.entities.read().unwrap();
+
+        let count = ro_entities.values().filter(|e| !is_deleted(*e)).count();
+
+        Ok(count)
+    }
+
+    async fn find(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Vec<T>, RepositoryError> {
+        let ro_entities = self.entities.read().unwrap();
+
+        let entities = ro_entities
+            .values()
+            .filter(|e| !is_deleted(*e) && specification.is_satisfied_by(e))
+            .cloned()
+            .collect();
+
+        Ok(entities)
+    }
+
+    async fn find_one(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<Option<T>, RepositoryError> {
+        let ro_entities = self.entities.read().unwrap();
+
+        let entity = ro_entities
+            .values()
+            .find(|e| !is_deleted(*e) && specification.is_satisfied_by(e))
+            .cloned();
+
+        Ok(entity)
+    }
+
+    async fn count_by(
+        &self,
+        specification: &dyn Specification<T>,
+    ) -> crate::Result<usize, RepositoryError> {
         let ro_entities = self.entities.read().unwrap();
 
-        Ok(ro_entities.len())
+        let count = ro_entities
+            .values()
+            .filter(|e| !is_deleted(*e) && specification.is_satisfied_by(e))
+            .count();
+
+        Ok(count)
     }
 }
 
@@ -63,23 +141,184 @@ impl<T: AggregateRoot + Clone> Repository<T> for InMemoryRepository<T>
 where
     <T as Entity>::Id: std::hash::Hash + Eq,
 {
-    async fn add(&self, entity: T) -> crate::Result<T> {
+    async fn add(&self, entity: T) -> crate::Result<T, RepositoryError> {
         let mut wo_entities = self.entities.write().unwrap();
 
-        wo_entities.insert(entity.id().clone(), entity.clone());
+        if wo_entities.contains_key(entity.id()) {
+            return Err(RepositoryError::AlreadyExists);
+        }
 
-        Ok(entity)
+        save(&mut wo_entities, entity)
+    }
+
+    async fn update(&self, entity: T) -> crate::Result<T, RepositoryError> {
+        let mut wo_entities = self.entities.write().unwrap();
+
+        if !exists(&wo_entities, entity.id()) {
+            return Err(RepositoryError::NotFound);
+        }
+
+        if let Some(versioned) = entity.as_versioned() {
+            check_version(&wo_entities, entity.id(), versioned.version())?;
+        }
+
+        save(&mut wo_entities, entity)
+    }
+
+    async fn delete(&self, entity: T) -> crate::Result<(), RepositoryError> {
+        let mut wo_entities = self.entities.write().unwrap();
+
+        let id = entity.id();
+
+        if !exists(&wo_entities, id) {
+            return Err(RepositoryError::NotFound);
+        }
+
+        if let Some(versioned) = entity.as_versioned() {
+            check_version(&wo_entities, id, versioned.version())?;
+        }
+
+        // The stored aggregate is marked as deleted, rather than the given one, whose unsaved
+        // modifications must not be persisted.
+        let stored = wo_entities.get_mut(id).unwrap();
+
+        match stored.as_soft_deletable_mut() {
+            Some(soft_deletable) => {
+                soft_deletable.set_deleted_at(Some(self.clock.now()));
+
+                if let Some(versioned) = stored.as_versioned_mut() {
+                    versioned.set_version(versioned.version() + 1);
+                }
+            }
+            None => {
+                wo_entities.remove(id);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: AggregateRoot + Clone> SoftDeleteRepository<T> for InMemoryRepository<T>
+where
+    <T as Entity>::Id: std::hash::Hash + Eq,
+{
+    async fn get_by_id_including_deleted(
+        &self,
+        id: <T as Entity>::Id,
+    ) -> crate::Result<Option<T>, RepositoryError> {
+        let ro_entities = self.entities.read().unwrap();
+
+        Ok(ro_entities.get(&id).cloned())
+    }
+
+    async fn restore(&self, id: <T as Entity>::Id) -> crate::Result<T, RepositoryError> {
+        let mut wo_entities = self.entities.write().unwrap();
+
+        let entity = wo_entities.get_mut(&id).ok_or(RepositoryError::NotFound)?;
+
+        if is_deleted(entity) {
+            if let Some(versioned) = entity.as_versioned_mut() {
+                versioned.set_version(versioned.version() + 1);
+            }
+
+            if let Some(soft_deletable) = entity.as_soft_deletable_mut() {
+                soft_deletable.set_deleted_at(None);
+            }
+        }
+
+        Ok(entity.clone())
     }
 
-    async fn update(&self, entity: T) -> crate::Result<T> {
-        self.add(entity).await
+    async fn list_including_deleted(
+        &self,
+        skip: usize,
+        take: usize,
+    ) -> crate::Result<Vec<T>, RepositoryError> {
+        let ro_entities = self.entities.read().unwrap();
+
+        let entities = ro_entities
+            .values()
+            .skip(skip)
+            .take(take)
+            .cloned()
+            .collect();
+
+        Ok(entities)
     }
 
-    async fn delete(&self, entity: T) -> crate::Result<()> {
+    async fn purge(&self, id: <T as Entity>::Id) -> crate::Result<(), RepositoryError> {
         let mut wo_entities = self.entities.write().unwrap();
 
-        wo_entities.remove(entity.id());
+        wo_entities.remove(&id).ok_or(RepositoryError::NotFound)?;
 
         Ok(())
     }
 }
+
+fn is_deleted<T: AggregateRoot>(entity: &T) -> bool {
+    entity.as_soft_deletable().is_some_and(|s| s.is_deleted())
+}
+
+fn exists<T: AggregateRoot>(
+    entities: &HashMap<<T as Entity>::Id, T>,
+    id: &<T as Entity>::Id,
+) -> bool
+where
+    <T as Entity>::Id: std::hash::Hash + Eq,
+{
+    entities.get(id).is_some_and(|e| !is_deleted(e))
+}
+
+fn save<T: AggregateRoot + Clone>(
+    entities: &mut HashMap<<T as Entity>::Id, T>,
+    mut entity: T,
+) -> crate::Result<T, RepositoryError>
+where
+    <T as Entity>::Id: std::hash::Hash + Eq,
+{
+    if let Some(invariants) = entity.as_invariants() {
+        invariants.check_invariants()?;
+    }
+
+    let changes = entity.pending_changes();
+
+    if let Some(versioned) = entity.as_versioned_mut() {
+        versioned.set_version(versioned.version() + changes);
+    }
+
+    if let Some(change_tracked) = entity.as_change_tracked_mut() {
+        change_tracked.clear_changes();
+    }
+
+    entity.clear_collection_changes();
+
+    let mut stored = entity.clone();
+
+    stored.clear_domain_events();
+
+    entities.insert(entity.id().clone(), stored);
+
+    Ok(entity)
+}
+
+fn check_version<T: AggregateRoot>(
+    entities: &HashMap<<T as Entity>::Id, T>,
+    id: &<T as Entity>::Id,
+    expected: u64,
+) -> crate::Result<(), RepositoryError>
+where
+    <T as Entity>::Id: std::hash::Hash + Eq,
+{
+    let actual = entities
+        .get(id)
+        .and_then(|e| e.as_versioned())
+        .map_or(0, |v| v.version());
+
+    if expected != actual {
+        return Err(ConcurrencyError { expected, actual }.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use crate::application::{ConcurrencyError, Saga, SagaStore};
+
+/// An in-memory implementation of [SagaStore], using a [HashMap].
+///
+/// See the example on [ProcessManager](crate::application::ProcessManager) for usage information
+/// of this saga store implementation.
+pub struct InMemorySagaStore<K, S> {
+    sagas: std::sync::RwLock<HashMap<K, Saga<K, S>>>,
+}
+
+impl<K, S> InMemorySagaStore<K, S> {
+    /// Creates a new [InMemorySagaStore].
+    pub fn new() -> Self {
+        Self {
+            sagas: std::sync::RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, S> Default for InMemorySagaStore<K, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl<K, S> SagaStore<K, S> for InMemorySagaStore<K, S>
+where
+    K: std::hash::Hash + Eq + Clone + Send + Sync,
+    S: Clone + Send + Sync,
+{
+    async fn save(&self, mut saga: Saga<K, S>) -> crate::Result<Saga<K, S>> {
+        let mut wo_sagas = self.sagas.write().unwrap();
+
+        let expected = saga.version;
+        let actual = wo_sagas.get(&saga.key).map_or(0, |s| s.version);
+
+        if expected != actual {
+            return Err(ConcurrencyError { expected, actual }.into());
+        }
+
+        saga.version += 1;
+
+        wo_sagas.insert(saga.key.clone(), saga.clone());
+
+        Ok(saga)
+    }
+
+    async fn load(&self, key: &K) -> crate::Result<Option<Saga<K, S>>> {
+        let ro_sagas = self.sagas.read().unwrap();
+
+        let saga = ro_sagas.get(key).cloned();
+
+        Ok(saga)
+    }
+
+    async fn find_expired(&self, now: SystemTime) -> crate::Result<Vec<Saga<K, S>>> {
+        let ro_sagas = self.sagas.read().unwrap();
+
+        let sagas = ro_sagas
+            .values()
+            .filter(|s| s.is_expired(now))
+            .cloned()
+            .collect();
+
+        Ok(sagas)
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use crate::application::SnapshotStore;
+use crate::domain::{Entity, EventSourced};
+
+/// An in-memory implementation of [SnapshotStore], using a [HashMap].
+///
+/// See the example on [EventSourcedRepository](crate::application::EventSourcedRepository) for
+/// usage information of this snapshot store implementation.
+pub struct InMemorySnapshotStore<T: EventSourced> {
+    snapshots: std::sync::RwLock<HashMap<<T as Entity>::Id, T>>,
+}
+
+impl<T: EventSourced> InMemorySnapshotStore<T> {
+    /// Creates a new [InMemorySnapshotStore].
+    pub fn new() -> Self {
+        Self {
+            snapshots: std::sync::RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: EventSourced> Default for InMemorySnapshotStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: EventSourced + Clone> SnapshotStore<T> for InMemorySnapshotStore<T>
+where
+    <T as Entity>::Id: std::hash::Hash + Eq,
+{
+    async fn save(&self, snapshot: T) -> crate::Result<()> {
+        let mut wo_snapshots = self.snapshots.write().unwrap();
+
+        wo_snapshots.insert(snapshot.id().clone(), snapshot);
+
+        Ok(())
+    }
+
+    async fn load(&self, id: <T as Entity>::Id) -> crate::Result<Option<T>> {
+        let ro_snapshots = self.snapshots.read().unwrap();
+
+        let snapshot = ro_snapshots.get(&id).cloned();
+
+        Ok(snapshot)
+    }
+}
@@ -7,22 +7,55 @@
 //!
 //! ## Application layer
 //!
+//! - [Clock](application::Clock)
+//! - [EventStore](application::EventStore)
+//!   - [UpcasterRegistry](application::UpcasterRegistry)
+//! - [ProcessManager](application::ProcessManager)
+//!   - [SagaRecoveryError](application::SagaRecoveryError)
+//!   - [SagaRunner](application::SagaRunner)
+//!   - [SagaStore](application::SagaStore)
+//! - [SnapshotStore](application::SnapshotStore)
 //! - [Repository](application::Repository)
+//!   - [EventSourcedRepository](application::EventSourcedRepository)
+//!   - [RepositoryError](application::RepositoryError)
+//!   - [SoftDeleteRepository](application::SoftDeleteRepository)
+//!   - [TenantRepository](application::TenantRepository)
 //! - Service:
 //!   - [Command](application::Command) / [Query](application::Query)
+//!   - [Mediator](application::Mediator)
 //!   - [Request](application::Request)
 //!   - [RequestHandler](application::RequestHandler)
 //!
 //! ## Domain layer
 //!
 //! - [AggregateRoot](domain::AggregateRoot)
+//!   - [AggregateRef](domain::AggregateRef)
+//!   - [ChangeTracked](domain::ChangeTracked)
+//!   - [DomainEvent](domain::DomainEvent)
+//!   - [DomainEventEnvelope](domain::DomainEventEnvelope)
+//!   - [EventSourced](domain::EventSourced)
+//!   - [Factory](domain::Factory)
+//!   - [Invariants](domain::Invariants)
+//!   - [SoftDeletable](domain::SoftDeletable)
+//!   - [TenantScoped](domain::TenantScoped)
+//!   - [Versioned](domain::Versioned)
 //! - [Entity](domain::Entity)
+//!   - [EntityCollection](domain::EntityCollection)
+//!   - [Identity](domain::Identity)
+//!   - [IdGenerator](domain::IdGenerator)
+//! - [DomainService](domain::DomainService)
+//!   - [AsyncDomainService](domain::AsyncDomainService)
+//! - [Specification](domain::Specification)
+//! - [StateMachine](domain::StateMachine)
 //! - [ValueObject](domain::ValueObject)
 //!
 //! ## Infrastructure layer
 //!
 //! - In-memory:
+//!   - [InMemoryEventStore](infrastructure::InMemoryEventStore)
 //!   - [InMemoryRepository](infrastructure::InMemoryRepository)
+//!   - [InMemorySagaStore](infrastructure::InMemorySagaStore)
+//!   - [InMemorySnapshotStore](infrastructure::InMemorySnapshotStore)
 
 #![warn(missing_docs)]
 
@@ -40,3 +73,77 @@ pub use error::*;
 
 #[cfg(feature = "derive")]
 pub use ddd_rs_derive::*;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    /// Implemented twice for aggregate roots, so that naming its item is ambiguous for them.
+    ///
+    /// Used by the `AggregateRoot` derive to reject fields whose type is another aggregate root.
+    pub trait AggregateRootsMustBeReferencedByIdentity<A> {
+        fn assert() {}
+    }
+
+    impl<T: ?Sized> AggregateRootsMustBeReferencedByIdentity<()> for T {}
+
+    pub struct IsAggregateRoot;
+
+    impl<T: ?Sized + crate::domain::AggregateRoot>
+        AggregateRootsMustBeReferencedByIdentity<IsAggregateRoot> for T
+    {
+    }
+
+    /// Field of an aggregate root, whose changes are cleared only if it is an `EntityCollection`.
+    ///
+    /// Used by the `AggregateRoot` derive, through [ClearEntityCollectionChanges] and its
+    /// [ClearFieldChanges] fallback, which method resolution tries in that order.
+    pub struct Field<'a, T>(pub &'a mut T);
+
+    pub trait ClearEntityCollectionChanges {
+        fn clear_changes(&mut self);
+    }
+
+    impl<T: crate::domain::Entity> ClearEntityCollectionChanges
+        for Field<'_, crate::domain::EntityCollection<T>>
+    {
+        fn clear_changes(&mut self) {
+            self.0.clear_changes();
+        }
+    }
+
+    pub trait ClearFieldChanges {
+        fn clear_changes(&mut self);
+    }
+
+    impl<T> ClearFieldChanges for &mut Field<'_, T> {
+        fn clear_changes(&mut self) {}
+    }
+
+    /// Aggregate root, exposed as `Invariants` only if it implements them.
+    ///
+    /// Used by the `AggregateRoot` derive, through [AsInvariants] and its [AsNoInvariants]
+    /// fallback, which method resolution tries in that order.
+    pub struct Wrap<'a, T>(pub &'a T);
+
+    pub trait AsInvariants<'a> {
+        fn as_invariants(&self) -> Option<&'a dyn crate::domain::Invariants>;
+    }
+
+    impl<'a, T: crate::domain::Invariants> AsInvariants<'a> for Wrap<'a, T> {
+        fn as_invariants(&self) -> Option<&'a dyn crate::domain::Invariants> {
+            Some(self.0)
+        }
+    }
+
+    pub trait AsNoInvariants<'a> {
+        fn as_invariants(&self) -> Option<&'a dyn crate::domain::Invariants>;
+    }
+
+    impl<'a, T> AsNoInvariants<'a> for &Wrap<'a, T> {
+        fn as_invariants(&self) -> Option<&'a dyn crate::domain::Invariants> {
+            None
+        }
+    }
+}